@@ -11,11 +11,12 @@ module Main where
 
 import qualified Crux
 import Cruces.CrucesMain
+import Cruces.Log (crucesLoggingToSayWhat, withCrucesLogging)
 import Paths_crucible_concurrency (version)
 
 main :: IO ()
 main = do
-  mkOutCfg <- Crux.defaultOutputConfig Crux.cruxLogMessageToSayWhat
-  Crux.withCruxLogMessage $
+  mkOutCfg <- Crux.defaultOutputConfig crucesLoggingToSayWhat
+  withCrucesLogging $
     Crux.loadOptions mkOutCfg "cruces" version cruciblesConfig
     $ run
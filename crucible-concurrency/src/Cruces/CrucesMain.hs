@@ -56,6 +56,7 @@ import Crucibles.Primitives
 import Crucibles.DPOR (DPOR)
 import Crucibles.ExploreTypes
 import Cruces.ExploreCrux
+import Cruces.Log
 
 data CrucesOptions = CrucesOptions
   { noDpor :: Bool
@@ -104,11 +105,11 @@ findMain mainName cs =
   where
     isFn x (ACFG _ _ g) = handleName (cfgHandle g) == x
 
-run :: Crux.Logs Crux.CruxLogMessage
+run :: Crux.Logs CrucesLogging
     => (Crux.CruxOptions, CrucesOptions)
     -> IO ()
 run (cruxOpts, opts) =
-  Crux.withCruxLogMessage $
+  withCrucesLogging $
   do let ?dpor = not (noDpor opts)
      let ?bound = maxPreemptions opts
      let fn = case Crux.inputFiles cruxOpts of
@@ -170,6 +171,7 @@ run (cruxOpts, opts) =
             return ()
 
 printCounterexamples ::
-  Crux.Logs Crux.CruxLogMessage =>
+  Crux.Logs msgs =>
+  Crux.SupportsCruxLogMessage msgs =>
   ProvedGoals -> IO ()
 printCounterexamples = Crux.logGoal
@@ -19,6 +19,7 @@ import           Control.Lens
 import           Data.Generics.Product.Fields (field, setField)
 import qualified Data.Vector as V
 import qualified Data.Map.Strict as Map
+import qualified Data.Text as Text
 import           System.IO (Handle)
 
 import           What4.Interface
@@ -42,13 +43,17 @@ import           Crucibles.Explore
 import           Crucibles.Scheduler
 import           Crucibles.Common
 
+import           Cruces.Log
+
 import           Crux.Goal (proveGoalsOnline)
 import           Crux.Types (totalProcessedGoals, provedGoals)
 
 -- | Callback for crucible-syntax exploration
-exploreCallback :: forall alg.
+exploreCallback :: forall alg msgs.
   (?bound::Int, SchedulingAlgorithm alg) =>
-  Crux.Logs Crux.CruxLogMessage =>
+  Crux.Logs msgs =>
+  Crux.SupportsCruxLogMessage msgs =>
+  SupportsCrucesLogMessage msgs =>
   Crux.CruxOptions ->
   HandleAllocator ->
   Handle ->
@@ -60,9 +65,8 @@ exploreCallback :: forall alg.
            , [Some C.GlobalVar]
            , FunctionBindings (ThreadExec alg s () C.UnitType) s ())
            ) ->
-  Crux.SimulatorCallbacks Crux.CruxLogMessage Crux.Types.CruxSimulationResult
+  Crux.SimulatorCallbacks msgs Crux.Types.CruxSimulationResult
 exploreCallback cruxOpts ha outh mkSym =
-  Crux.withCruxLogMessage $
   Crux.SimulatorCallbacks $
     return $
       Crux.SimulatorHooks
@@ -95,6 +99,14 @@ emptyExploration = Exploration { _exec      = initialExecutions
                                , _schedAlg  = initialAlgState
                                , _num       = 0
                                , _gVars     = mempty
+                               , _dataAccesses  = mempty
+                               , _joinedThreads = mempty
+                               , _releases      = mempty
+                               , _condWakeups   = mempty
+                               , _stepEnds      = mempty
+                               , _atomicBlock   = Nothing
+                               , _threadLocalVars = mempty
+                               , _threadLocals  = mempty
                                }
   where
     s0 = Scheduler { _threads      = V.fromList [EmptyThread]
@@ -109,6 +121,7 @@ emptyExploration = Exploration { _exec      = initialExecutions
 exploreOvr :: forall sym bak ext alg ret rtp msgs.
   Crux.Logs msgs =>
   Crux.SupportsCruxLogMessage msgs =>
+  SupportsCrucesLogMessage msgs =>
   (?bound::Int, IsSymBackend sym bak, IsSyntaxExtension ext, SchedulingAlgorithm alg, RegValue sym ret ~ ()) =>
   bak ->
   Maybe (Crux.SomeOnlineSolver sym bak) ->
@@ -164,6 +177,10 @@ exploreOvr bak symOnline cruxOpts mainAct =
         let amDone = fullyExplored (exc { _currentEventID = 0 }) alg
         provedAllGoals <- checkGoals
 
+        when (not provedAllGoals) $
+           do locs <- use (stateExpl.stepEnds)
+              liftIO $ sayCruces $ FailingInterleaving $ map Text.pack $ ppInterleaving exc locs
+
         if amDone || not provedAllGoals then
           return ()
         else
@@ -182,4 +199,8 @@ exploreOvr bak symOnline cruxOpts mainAct =
          stateExpl.scheduler.numSwitches  .= 0
          -- Per-run exploration bookkeeping
          runUpdateSchedAlg prepareNewExecution
+         stateExpl.condWakeups            .= mempty
+         stateExpl.stepEnds               .= mempty
+         stateExpl.atomicBlock            .= Nothing
+         stateExpl.threadLocals           .= mempty
          stateExec.birthdays              .= Map.fromList [(ThreadID 0, 0)]
@@ -0,0 +1,69 @@
+{-# LANGUAGE ConstraintKinds #-}
+{-# LANGUAGE DeriveAnyClass #-}
+{-# LANGUAGE DeriveGeneric #-}
+{-# LANGUAGE ImplicitParams #-}
+{-# LANGUAGE OverloadedStrings #-}
+{-# LANGUAGE RankNTypes #-}
+
+module Cruces.Log
+  ( CrucesLogMessage (..),
+    SupportsCrucesLogMessage,
+    crucesLogMessageToSayWhat,
+    sayCruces,
+    CrucesLogging (..),
+    crucesLoggingToSayWhat,
+    withCrucesLogging,
+  )
+where
+
+import Data.Aeson (ToJSON)
+import qualified Data.Text as T
+import GHC.Generics (Generic)
+
+import Crux.Log (SayLevel (..), SayWhat (..))
+import qualified Crux.Log as Log
+
+data CrucesLogMessage
+  = FailingInterleaving [T.Text]
+  -- ^ The steps of a failing execution, one per line
+  deriving (Generic, ToJSON)
+
+type SupportsCrucesLogMessage msgs =
+  (?injectCrucesLogMessage :: CrucesLogMessage -> msgs)
+
+sayCruces ::
+  Log.Logs msgs =>
+  SupportsCrucesLogMessage msgs =>
+  CrucesLogMessage ->
+  IO ()
+sayCruces msg =
+  let ?injectMessage = ?injectCrucesLogMessage
+   in Log.say msg
+
+crucesLogTag :: T.Text
+crucesLogTag = "Cruces"
+
+crucesLogMessageToSayWhat :: CrucesLogMessage -> SayWhat
+crucesLogMessageToSayWhat (FailingInterleaving steps) =
+  SayWhat Fail crucesLogTag $
+    T.unlines ("Interleaving of the failing execution:" : steps)
+
+-- | The messages logged by `cruces` itself.
+data CrucesLogging
+  = LoggingCrux Log.CruxLogMessage
+  | LoggingCruces CrucesLogMessage
+  deriving (Generic, ToJSON)
+
+crucesLoggingToSayWhat :: CrucesLogging -> SayWhat
+crucesLoggingToSayWhat (LoggingCrux msg) = Log.cruxLogMessageToSayWhat msg
+crucesLoggingToSayWhat (LoggingCruces msg) = crucesLogMessageToSayWhat msg
+
+withCrucesLogging ::
+  ( ( Log.SupportsCruxLogMessage CrucesLogging
+    , SupportsCrucesLogMessage CrucesLogging
+    ) => computation
+  ) -> computation
+withCrucesLogging computation =
+  let ?injectCruxLogMessage = LoggingCrux
+      ?injectCrucesLogMessage = LoggingCruces
+   in computation
@@ -19,6 +19,7 @@ import Lang.Crucible.Simulator
 import Lang.Crucible.FunctionHandle (FnHandle)
 import Lang.Crucible.Simulator.ExecutionTree
 import What4.Interface (Pred)
+import What4.ProgramLoc (ProgramLoc)
 
 import Crucibles.Execution
 
@@ -34,6 +35,10 @@ data ResumeCond = Resumable !(Maybe (Bool, Set.Set Text))
                 -- ^ the condition variable + mutex + whether or not we've been notified
                 -- (This is highly specialized to condition variables -- perhaps this should be
                 -- folded into the more general Condition form)
+                | OnDataAccess !Bool !(Set.Set Text) !ProgramLoc
+                -- ^ Can be resumed, and will then perform a non-atomic access
+                -- (a write if the Bool is True) to the given resources at the
+                -- given location. These accesses are checked for data races.
                 deriving (Show, Eq)
 
 data ResumePred =
@@ -93,6 +98,7 @@ condResource = \case
   OnCond cv m _  -> Set.fromList [Resource cv, Resource m]
   OnJoin tid     -> Set.singleton (Thread tid)
   OnPred rs m    -> resumePredResource m <> Set.map Resource rs
+  OnDataAccess _ rs _ -> Set.map Resource rs
 
 -- | The resources mentioned by the 'ResumePred'
 resumePredResource :: ResumePred -> Set.Set (Resource Text)
@@ -132,6 +138,7 @@ tsEventInfo ts dir =
         OnCond gv m _                  -> [Write (Set.fromList [gv, m])]
         Resumable (Just (ro, r))       -> [if ro then Read r else Write r]
         Resumable Nothing              -> []
+        OnDataAccess w r _             -> [if w then Write r else Read r]
 
 -- | Pretty print the thread state (useful for debugging).
 ppThreadState :: ThreadStateP p sym ext ret -> [Char]
@@ -0,0 +1,122 @@
+{- |
+Module           : Crucibles.DataRace
+Description      : Data race detection over a single execution
+Copyright        : (c) Galois, Inc 2021
+Maintainer       : Alexander Bakst <abakst@galois.com>
+
+Two non-atomic accesses to the same resource race if they are made by different
+threads, at least one of them is a write, and neither happens before the other.
+
+The happens-before relation is computed with vector clocks, using event IDs as
+timestamps (event IDs increase along any execution). Every event advances the
+clock of its thread. An event that is not itself a non-atomic access (a lock
+acquisition, an atomic access, ...) synchronizes through its resources: the
+thread's clock is joined with the clock of each resource, and each resource
+takes the result. Releasing a lock is not an event, so releases are recorded
+separately, by the event after which they happen. A new thread starts with the
+clock of the event that spawned it, and a thread that finishes joining another
+acquires that thread's clock.
+-}
+module Crucibles.DataRace
+  ( DataAccess(..)
+  , findDataRace
+  ) where
+
+import           Control.Lens
+import           Data.Foldable (foldl')
+import qualified Data.IntMap.Strict as IntMap
+import           Data.IntMap.Strict (IntMap)
+import qualified Data.Map.Strict as Map
+import           Data.Map.Strict (Map)
+import qualified Data.Set as Set
+import           Data.Set (Set)
+import           Data.Maybe (fromMaybe, listToMaybe)
+import           Data.Text (Text)
+
+import           Crucibles.ClockVector
+import           Crucibles.Execution
+
+-- | A non-atomic access that happened earlier in the execution
+data DataAccess =
+  DataAccess { accessEvent    :: !EventID
+             , accessThread   :: !ThreadID
+             , accessWrite    :: !Bool
+             , accessResource :: !Text
+             }
+  deriving (Show)
+
+type Clock = ClockVector ThreadID EventID
+
+data RaceState =
+  RaceState { threadClocks   :: !(Map ThreadID Clock)
+            , resourceClocks :: !(Map Text Clock)
+            , eventClocks    :: !(IntMap Clock)   -- ^ The clock after each event
+            , accesses       :: ![DataAccess]    -- ^ Most recent first
+            }
+
+-- | Find an earlier non-atomic access in the given execution that races with a
+-- non-atomic access the given thread is about to make, returning the most recent
+-- such access.
+findDataRace ::
+  IntMap a {-^ The events that are non-atomic accesses -} ->
+  IntMap ThreadID {-^ The events that finish a join, with the joined thread -} ->
+  IntMap (Set Text) {-^ The locks released by each event's thread after it -} ->
+  Map ThreadID EventID {-^ The event at which each thread was spawned -} ->
+  [ScheduleEvent EventInfo] {-^ The execution so far, oldest event first -} ->
+  ThreadID {-^ The thread making the new access -} ->
+  Bool {-^ Is the new access a write? -} ->
+  Set Text {-^ The resources accessed -} ->
+  Maybe DataAccess
+findDataRace dataEvents joins releases bdays trace tid isWrite rs =
+  listToMaybe [ a | a <- accesses st
+                  , accessThread a /= tid
+                  , isWrite || accessWrite a
+                  , accessResource a `Set.member` rs
+                  , not (accessEvent a <= clock ^. cvAt (accessThread a))
+                  ]
+  where
+    st    = foldl' step st0 trace
+    st0   = RaceState mempty mempty mempty []
+    clock = fromMaybe cvEmpty (Map.lookup tid (threadClocks st))
+
+    step s ev =
+      let t    = ev ^. eventThread
+          eid  = ev ^. eventID
+          info = ev ^. eventInfo
+          cv0  = fromMaybe (startClock s t) (Map.lookup t (threadClocks s))
+          cv1  = case IntMap.lookup eid joins of
+                   Just u  -> maxClockVector cv0 (fromMaybe cvEmpty (Map.lookup u (threadClocks s)))
+                   Nothing -> cv0
+          isData = IntMap.member eid dataEvents
+          syncs  = if isData then [] else Set.toList (accessedResources info)
+          cv2  = maxClockVectors (cv1 : [ fromMaybe cvEmpty (Map.lookup r (resourceClocks s)) | r <- syncs ])
+          cv   = cv2 & cvAt t .~ eid
+          rels = maybe [] Set.toList (IntMap.lookup eid releases)
+          new  = if isData
+                   then [ DataAccess eid t (isWriteEvent info) r
+                        | r <- Set.toList (accessedResources info) ]
+                   else []
+      in s { threadClocks   = Map.insert t cv (threadClocks s)
+           , resourceClocks = foldl' (\m r -> Map.insertWith maxClockVector r cv m)
+                                 (foldl' (\m r -> Map.insert r cv m) (resourceClocks s) syncs)
+                                 rels
+           , eventClocks    = IntMap.insert eid cv (eventClocks s)
+           , accesses       = new ++ accesses s
+           }
+
+    -- A thread's first event is preceded by everything before its spawn.
+    startClock s t =
+      fromMaybe cvEmpty $
+        do b <- Map.lookup t bdays
+           IntMap.lookup b (eventClocks s)
+
+    accessedResources info =
+      case info of
+        Write ws -> ws
+        Read ws  -> ws
+        _        -> mempty
+
+    isWriteEvent info =
+      case info of
+        Write _ -> True
+        _       -> False
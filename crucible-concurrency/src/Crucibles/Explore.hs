@@ -34,7 +34,7 @@ should be selected after the execution of a given hook.
 {-# LANGUAGE ImplicitParams #-}
 {-# OPTIONS_GHC -Wno-unused-local-binds #-}
 {-# LANGUAGE FlexibleContexts #-}
-module Crucibles.Explore ( scheduleFeature, ppScheduler ) where
+module Crucibles.Explore ( scheduleFeature, ppScheduler, ppInterleaving ) where
 
 import           Control.Lens
 import           Control.Monad.IO.Class
@@ -45,11 +45,13 @@ import qualified Data.IntSet as IntSet
 import qualified Data.Map.Strict as Map
 import qualified Data.IntMap as IntMap
 import qualified Data.Parameterized.Context as Ctx
-import           Data.Parameterized.NatRepr
 import           Data.Text (Text)
+import qualified Data.Text as Text
 import qualified Data.Vector as V
 
 import Data.Foldable (foldlM)
+import Data.List (intercalate, sortOn)
+import Data.Maybe (fromMaybe)
 import           GHC.Stack
 
 import           Lang.Crucible.Backend
@@ -63,8 +65,10 @@ import           Lang.Crucible.Simulator.EvalStmt
 import           Lang.Crucible.Simulator.ExecutionTree
 import           What4.Interface
 import           What4.Config
+import           What4.ProgramLoc
 
 import           Crucibles.Common
+import           Crucibles.DataRace
 import           Crucibles.Execution
 import           Crucibles.ExploreTypes
 import           Crucibles.Scheduler
@@ -181,9 +185,12 @@ scheduleCall prims globs rh@(ReturnToCrucible tpr rest) x cf@CallFrame { _frameC
             (maybe ExecutionFeatureNoChange ExecutionFeatureNewState
                <$> yieldThread globs ts0)
 
-    Just (ThreadCondSignal cv) ->
+    Just (ThreadCondSignal wakeAll cv) ->
         evalTEWithState s $
-          do stateExpl.scheduler.threads %= V.map (notifyThread cv)
+          do if wakeAll
+               then do stateExpl.scheduler.threads %= V.map (notifyThread cv)
+                       stateExpl.condWakeups %= Map.delete cv
+               else notifyOne cv
              ts0 <- gets (saveThreadState (Resumable (Just (False, Set.singleton cv))) rh)
              s'  <- yieldThread globs ts0
              return $ maybe ExecutionFeatureNoChange ExecutionFeatureNewState s'
@@ -194,6 +201,18 @@ scheduleCall prims globs rh@(ReturnToCrucible tpr rest) x cf@CallFrame { _frameC
            scheduleThreadCreate ty retTy tpr fh arg mkRet rest
      | otherwise -> error "Unexpected return type for thread create primitive"
 
+    Just (ThreadSelf retTy mkRet)
+     | Just Refl <- testEquality retTy tpr ->
+         evalTEWithState s $
+           scheduleThreadSelf tpr mkRet rest
+     | otherwise -> error "Unexpected return type for thread self primitive"
+
+    Just (ThreadAtomic begin)
+     | Just Refl <- testEquality C.UnitRepr tpr ->
+         evalTEWithState s $
+           scheduleAtomic begin rest
+     | otherwise -> error "Unexpected return type for atomic block primitive"
+
 
     Just (ThreadFinish (C.Some val)) ->
        maybeTerminate globs (CallState rh call) (CompletedThread val) s
@@ -255,8 +274,15 @@ scheduleYield globs rh call spec mods ro =
 
     Release l ->
       do setInternalGlobal l C.BoolRepr (return . truePred)
+         curr <- use (stateExec.currentEventID)
+         stateExpl.releases %= IntMap.insertWith Set.union curr (Set.singleton l)
          es <- gets (CallState rh call)
          return $ ExecutionFeatureModifiedState es
+
+    NonAtomic ->
+      do sym <- use (stateContext.ctxSymInterface)
+         loc <- liftIO $ getCurrentProgramLoc sym
+         runYield $ OnDataAccess (not ro) modSet loc
   where
     modSet = Set.fromList mods
 
@@ -295,6 +321,76 @@ scheduleThreadCreate ty retTy tpr fh arg mkRet rest =
                 st' = s' & stateTree.actFrame.gpValue .~ MF f'
             return (ExecutionFeatureNewState $ RunningState (RunReturnFrom "spawn") st')
 
+-- | Return the ID of the running thread, without yielding.
+scheduleThreadSelf ::
+  ( SchedulerConstraints sym ext alg
+  , rtp ~ RegEntry sym ret
+  ) =>
+  C.TypeRepr tp {-^ The type of the returned ID -} ->
+  (sym -> Int -> IO (RegValue sym tp)) {-^ How to construct the return value from the thread's ID -} ->
+  C.StmtSeq ext blocks rty (ctx C.::> tp) {-^ Continuation of the caller -} ->
+  ThreadExecM alg sym ext ret root (CrucibleLang blocks rty) ('Just ctx)
+    (ExecutionFeatureResult (ThreadExec alg sym ext ret) sym ext root)
+scheduleThreadSelf tpr mkRet rest =
+  do tid <- use (stateExpl.scheduler.activeThread)
+     s' <- get
+     case s' ^. stateTree.actFrame.gpValue of
+       MF f ->
+         do sym <- use (stateContext.ctxSymInterface)
+            ret <- liftIO $ mkRet sym tid
+            let f' = extendFrame tpr ret rest f
+                st' = s' & stateTree.actFrame.gpValue .~ MF f'
+            return (ExecutionFeatureNewState $ RunningState (RunReturnFrom "current_thread") st')
+
+-- | Begin or end an atomic block in the running thread, without yielding.
+scheduleAtomic ::
+  ( SchedulerConstraints sym ext alg
+  , rtp ~ RegEntry sym ret
+  ) =>
+  Bool {-^ Begin a block, rather than end one -} ->
+  C.StmtSeq ext blocks rty (ctx C.::> C.UnitType) {-^ Continuation of the caller -} ->
+  ThreadExecM alg sym ext ret root (CrucibleLang blocks rty) ('Just ctx)
+    (ExecutionFeatureResult (ThreadExec alg sym ext ret) sym ext root)
+scheduleAtomic begin rest =
+  do tid <- use (stateExpl.scheduler.activeThread)
+     blk <- use (stateExpl.atomicBlock)
+     let blk' = case (begin, blk) of
+           (True, Nothing) -> Just (Just (tid, 1))
+           (True, Just (t, n)) | t == tid -> Just (Just (t, n + 1))
+           (False, Just (t, n)) | t == tid -> Just (if n > 1 then Just (t, n - 1) else Nothing)
+           _ -> Nothing
+     case blk' of
+       Nothing -> ExecutionFeatureNewState <$> reportUnbalancedAtomic begin tid
+       Just b ->
+         do stateExpl.atomicBlock .= b
+            s' <- get
+            case s' ^. stateTree.actFrame.gpValue of
+              MF f ->
+                do let f' = extendFrame C.UnitRepr () rest f
+                       st' = s' & stateTree.actFrame.gpValue .~ MF f'
+                   return (ExecutionFeatureNewState $ RunningState (RunReturnFrom "atomic_block") st')
+
+-- | Fail the current execution because the given thread began an atomic block
+-- inside another thread's, or ended one that it isn't in.
+reportUnbalancedAtomic ::
+  SchedulerConstraints sym ext alg =>
+  Bool {-^ The thread was beginning a block, rather than ending one -} ->
+  Int {-^ The thread -} ->
+  ThreadExecM alg sym ext ret rtp f a (ExecState (ThreadExec alg sym ext ret) sym ext rtp)
+reportUnbalancedAtomic begin tid =
+  use stateContext >>= \ctx -> withBackend ctx $ \bak ->
+  do sym <- use (stateContext.ctxSymInterface)
+     loc <- liftIO $ getCurrentProgramLoc sym
+     let msg
+           | begin = "unbalanced atomic block: thread " ++ show tid ++
+                     " began an atomic block inside another thread's"
+           | otherwise = "unbalanced atomic block: thread " ++ show tid ++
+                         " ended an atomic block it isn't in"
+         simerr = SimError loc (GenericSimError msg)
+     liftIO $ addProofObligation bak (LabeledPred (falsePred sym) simerr)
+     s <- get
+     liftIO $ runReaderT (abortExec (AssertionFailure simerr)) s
+
 -- | Consult the Scheduling Algorithm for the next thread to run.
 yieldThread ::
   ( SchedulerConstraints sym ext alg
@@ -306,14 +402,22 @@ yieldThread ::
   ThreadExecM alg sym ext ret rtp f a (Maybe (ExecState (ThreadExec alg sym ext ret) sym ext rtp))
 yieldThread globs ts0 =
   do setActiveThreadState (stateExpl.scheduler) ts0 -- Critical! We want to be able to pick this thread
+     -- Remember where the current step ended, for 'ppInterleaving'
+     stepLoc <- liftIO . getCurrentProgramLoc =<< use (stateContext.ctxSymInterface)
+     step    <- use (stateExec.currentEventID)
+     stateExpl.stepEnds %= IntMap.insert step stepLoc
      ts <- runnableThreads globs
      if null ts
        -- If there are no runnable threads, then just return the (hopefully)
-       -- already stored main result
+       -- already stored main result, unless the main thread is still waiting on
+       -- something, in which case no thread can make progress.
        then do -- res <- use (stateExpl.scheduler.to mainCont)
                curr <- use (stateExec.currentEventID)
                stateExec.maximalEvents %= IntSet.insert curr
-               return Nothing
+               thds <- use (stateExpl.scheduler.threads)
+               case thds V.!? 0 of
+                 Just RunningThread{} -> Just <$> reportDeadlock thds
+                 _ -> return Nothing
 
        -- Otherwise mark backtracking points and pick a new thread
        else do me     <- use (stateExpl.scheduler.activeThread)
@@ -374,12 +478,46 @@ switchToPendingThread globals preempted (tid, dir) =
      stateExec.currentEventID .= e ^. eventID
      when preempted $ stateExpl.scheduler.numSwitches %= (+1)
      n <- use (stateExpl.scheduler.numSwitches)
-     blocked <- not <$> checkRunnable globals tstate
+     blocked <- not <$> checkRunnable globals tid tstate
      if (?bound > 0 && n > ?bound) || blocked
        -- TODO: Re-evaluate if returning a Maybe value here is ever useful.
         then Just <$> abortInfeasible
         else do assertRunnable globals tid tstate e
-                Just <$> resumeThreadState globals tid tstate dir
+                race <- noteEvent tid tstate e
+                case race of
+                  Just (other, otherLoc) ->
+                    Just <$> reportDataRace tid tstate other otherLoc
+                  Nothing ->
+                    Just <$> resumeThreadState globals tid tstate dir
+
+-- | Record what we need to know about the event that resumes the given thread
+-- in order to detect data races. If the thread is about to make a non-atomic
+-- access, returns the earlier access (and its location) that it races with, if
+-- any.
+noteEvent ::
+  ThreadID ->
+  ThreadState alg sym ext ret ->
+  ThreadEvent ->
+  ThreadExecM alg sym ext ret r f a (Maybe (DataAccess, ProgramLoc))
+noteEvent tid tstate e =
+  case tstate of
+    RunningThread (OnJoin u) _ _ ->
+      do stateExpl.joinedThreads %= IntMap.insert eid u
+         return Nothing
+    RunningThread (OnDataAccess isWrite rs loc) _ _ ->
+      do stateExpl.dataAccesses %= IntMap.insert eid loc
+         accs  <- use (stateExpl.dataAccesses)
+         joins <- use (stateExpl.joinedThreads)
+         rels  <- use (stateExpl.releases)
+         exe   <- use stateExec
+         let trace = filter ((/= eid) . view eventID) (currentTrace exe)
+         return $
+           do other <- findDataRace accs joins rels (exe ^. birthdays) trace tid isWrite rs
+              otherLoc <- IntMap.lookup (accessEvent other) accs
+              return (other, otherLoc)
+    _ -> return Nothing
+  where
+    eid = e ^. eventID
 
 -- | Given a thread ID and state for that thread, resume execution of that
 -- thread from that state. Fails with an error if the thread is not actually
@@ -394,7 +532,8 @@ resumeThreadState ::
   Direction ->
   ThreadExecM alg sym ext ret rtp f a (ExecState (ThreadExec alg sym ext ret) sym ext rtp)
 resumeThreadState globalVars tID ts dir =
-  do stateExpl.scheduler.activeThread .= threadID tID
+  do switchThreadLocals (threadID tID)
+     stateExpl.scheduler.activeThread .= threadID tID
      case ts of
        CompletedThread {} ->
          error "resumeThreadState: trying to resume a completed thread!"
@@ -407,11 +546,14 @@ resumeThreadState globalVars tID ts dir =
 
        RunningThread resumeCond retHandler stack
          | dir == NoDirection ->
-           do isRunnable <- checkRunnable globalVars ts
+           do isRunnable <- checkRunnable globalVars tID ts
               if isRunnable then
                 do case resumeCond of
                      OnPred _ (SchedulerVar gvName) ->
                        setInternalGlobal gvName C.BoolRepr (return . falsePred)
+                     -- Take the notify-one wakeup that made this thread runnable.
+                     OnCond cv _ False ->
+                       stateExpl.condWakeups %= Map.update (takeWakeup (threadID tID)) cv
                      _ -> return ()
                    restoreRunningThread retHandler stack
               else error "Restoring a thread that is not runnable"
@@ -421,6 +563,22 @@ resumeThreadState globalVars tID ts dir =
        BranchingThread p stk tframe fframe ->
          restoreBranchingThread tID dir p stk tframe fframe
 
+-- | Save the thread-local globals of the running thread, and restore those of
+-- the given thread, if it has run before.
+switchThreadLocals :: Int -> ThreadExecM alg sym ext ret r f a ()
+switchThreadLocals new =
+  do old  <- use (stateExpl.scheduler.activeThread)
+     vars <- use (stateExpl.threadLocalVars)
+     unless (old == new || null vars) $
+       do globs <- use (stateTree.actFrame.gpGlobals)
+          saved <- use (stateExpl.threadLocals)
+          stateExpl.threadLocals .= IntMap.insert old globs saved
+          forM_ (IntMap.lookup new saved) $ \theirs ->
+            stateTree.actFrame.gpGlobals %= \gs -> foldl (restore theirs) gs vars
+  where
+    restore theirs gs (C.Some gv) =
+      maybe gs (\v -> insertGlobal gv v gs) (lookupGlobal gv theirs)
+
 -- | Starts a new thread, passing the given RegValue as its argument.
 startNewThread ::
   ( SchedulerConstraints sym ext alg
@@ -475,9 +633,10 @@ restoreRunningThread (ReturnToCrucible retType rest) (ActiveTree ctx pres) =
      case pres ^. partialValue.gpValue of
        MF f ->
          case ts of -- TODO: make this a parameter
+           -- The joined thread's return value, which may be of any type, is
+           -- the result of the join.
            RunningThread (OnJoin blockingThread) _ _
              | CompletedThread retVal <- thds V.! threadID blockingThread
-             , Just Refl <- testEquality retType (C.BVRepr (knownNat @32))
              , Just Refl <- testEquality retType (regType retVal) ->
                do let s' = st & (stateTree.actFrame.gpGlobals .~ st ^. stateTree.actFrame.gpGlobals)
                               . (stateTree .~ ActiveTree ctx (pres & partialValue . gpValue .~ MF f'))
@@ -557,6 +716,79 @@ returnFinishedResult mres =
               do putStrLn "<deadlock>"
                  runReaderT (abortExec (AssertionFailure simerr)) s
 
+-- | Fail the current execution because every unfinished thread is blocked.
+-- The blocked threads are listed in the error message.
+reportDeadlock ::
+  ( SchedulerConstraints sym ext alg
+  , rtp ~ RegEntry sym ret
+  ) =>
+  V.Vector (ThreadState alg sym ext ret) ->
+  ThreadExecM alg sym ext ret rtp f a (ExecState (ThreadExec alg sym ext ret) sym ext rtp)
+reportDeadlock thds =
+  use stateContext >>= \ctx -> withBackend ctx $ \bak ->
+  do sym <- use (stateContext.ctxSymInterface)
+     loc <- liftIO $ getCurrentProgramLoc sym
+     let blocked = [ "thread " ++ show i ++ " " ++ ppThreadState t
+                   | (i, t@RunningThread{}) <- zip [0 :: Int ..] (V.toList thds) ]
+         msg     = "deadlock: all threads are blocked: " ++ intercalate ", " blocked
+         simerr  = SimError loc (GenericSimError msg)
+     liftIO $ addProofObligation bak (LabeledPred (falsePred sym) simerr)
+     s <- get
+     liftIO $ runReaderT (abortExec (AssertionFailure simerr)) s
+
+-- | Fail the current execution because the non-atomic access the given thread
+-- is about to make races with an earlier access by another thread.
+reportDataRace ::
+  ( SchedulerConstraints sym ext alg
+  , rtp ~ RegEntry sym ret
+  ) =>
+  ThreadID ->
+  ThreadState alg sym ext ret ->
+  DataAccess ->
+  ProgramLoc ->
+  ThreadExecM alg sym ext ret rtp f a (ExecState (ThreadExec alg sym ext ret) sym ext rtp)
+reportDataRace tid tstate other otherLoc =
+  use stateContext >>= \ctx -> withBackend ctx $ \bak ->
+  do sym <- use (stateContext.ctxSymInterface)
+     let (isWrite, loc) =
+           case tstate of
+             RunningThread (OnDataAccess w _ l) _ _ -> (w, l)
+             _ -> error "reportDataRace: thread is not making a non-atomic access"
+         ppAccess t w l =
+           (if w then "write" else "read") ++ " by thread " ++ show (threadID t) ++
+           " at " ++ show (plSourceLoc l)
+         msg = "data race on " ++ Text.unpack (accessResource other) ++ ": " ++
+               ppAccess tid isWrite loc ++ " conflicts with " ++
+               ppAccess (accessThread other) (accessWrite other) otherLoc
+         simerr = SimError loc (GenericSimError msg)
+     liftIO $ addProofObligation bak (LabeledPred (falsePred sym) simerr)
+     s <- get
+     liftIO $ runReaderT (abortExec (AssertionFailure simerr)) s
+
+-- | Describe the steps of the current execution, oldest first: the thread that
+-- ran each step, what it did at the start of the step, and where it reached
+-- its next scheduling point, one line per step. This is logged when an
+-- execution fails, so that the interleaving that led to the failure can be
+-- reproduced.
+ppInterleaving :: ThreadExecutions -> IntMap.IntMap ProgramLoc -> [String]
+ppInterleaving exe locs = map ppStep (currentTrace exe)
+  where
+    ppStep e =
+      "  thread " ++ show (threadID (e ^. eventThread)) ++ ": " ++
+      ppInfo (e ^. eventInfo) ++
+      maybe "" (\l -> ", then ran to " ++ show (plSourceLoc l)) (IntMap.lookup (e ^. eventID) locs)
+
+    ppInfo info =
+      case info of
+        Write rs    -> "write " ++ ppResources rs
+        Read rs     -> "read " ++ ppResources rs
+        ThreadSpawn -> "start"
+        ThreadExit  -> "exit"
+        Join        -> "finish joining"
+        Branch b    -> "branch " ++ if b then "taken" else "not taken"
+
+    ppResources = intercalate ", " . map Text.unpack . Set.toList
+
 -- | ThreadState helpers
 
 -- | The ThreadState corresponding to a thread executing @join@
@@ -656,17 +888,54 @@ notifyThread gv ts =
       | gv == gv' -> RunningThread (OnCond gv' m True) rh stk
     _ -> ts
 
+-- | Let one of the threads waiting on a condition variable wake up. Every
+-- thread waiting now becomes runnable, and the first one to be scheduled takes
+-- the wakeup, so exploring schedules explores the choice of thread. Threads
+-- that start waiting later can't take it. A signal with no thread left to wake
+-- is lost.
+notifyOne :: Text -> ThreadExecM alg sym ext ret r f a ()
+notifyOne cv =
+  do thds <- use (stateExpl.scheduler.threads)
+     let waiting = IntSet.fromList
+                     [ i | (i, RunningThread (OnCond cv' _ False) _ _) <- zip [0..] (V.toList thds)
+                         , cv' == cv ]
+     stateExpl.condWakeups %= Map.alter (wake waiting) cv
+  where
+    wake waiting k
+      | length pending < IntSet.size waiting = Just (pending ++ [waiting])
+      | otherwise = k
+      where pending = fromMaybe [] k
+
+-- | Remove a wakeup that the given thread can take from the pending wakeups of
+-- a condition variable. If there are several, this takes the one that the
+-- fewest threads can take, and of those the oldest, to leave the others to the
+-- threads that need them. The thread can't take the others too: if it waits
+-- again, it starts waiting after they were sent, so it's removed from them, and
+-- a wakeup that no thread is left to take is lost.
+takeWakeup :: Int -> [IntSet] -> Maybe [IntSet]
+takeWakeup tid pending =
+  case sortOn (IntSet.size . snd) [ (j, w) | (j, w) <- zip [0 :: Int ..] pending, IntSet.member tid w ] of
+    [] -> nonEmpty pending
+    (j, _) : _ -> nonEmpty [ w' | (j', w) <- zip [0..] pending, j' /= j
+                                , let w' = IntSet.delete tid w, not (IntSet.null w') ]
+  where
+    nonEmpty [] = Nothing
+    nonEmpty ws = Just ws
+
 -- | Return True if the given @ThreadState@ denotes an executable thread
 checkRunnable ::
   IsSymInterface sym =>
   [Some GlobalVar] ->
+  ThreadID ->
   ThreadState alg sym ext ret ->
   ThreadExecM alg sym ext ret r f a Bool
-checkRunnable globs ts =
+checkRunnable globs tid ts =
   do ths <- use (stateExpl.scheduler.threads)
      ourglobs <- use (stateExpl.gVars)
      globState <- use stateGlobals
-     return (runnable globState ourglobs globs ths ts)
+     wakeups <- use (stateExpl.condWakeups)
+     blk <- use (stateExpl.atomicBlock)
+     return (atomicAllows ths blk (threadID tid) && runnable globState ourglobs wakeups globs ths (threadID tid) ts)
 
 -- | Exit with an error if the given @ThreadState@ is is not runnable
 assertRunnable ::
@@ -677,7 +946,7 @@ assertRunnable ::
   ScheduleEvent EventInfo ->
   ThreadExecM alg sym ext ret r f a ()
 assertRunnable globs thID thNext e =
-  do canRun <- checkRunnable globs thNext
+  do canRun <- checkRunnable globs thID thNext
      unless canRun $
        do me  <- use (stateExpl.scheduler.activeThread)
           liftIO $ putStrLn ("Thread: " ++ show me)
@@ -692,18 +961,45 @@ runnableThreads ::
 runnableThreads globs =
   do globState <- use stateGlobals
      ourglobs  <- use (stateExpl.gVars)
-     selectThreads (stateExpl.scheduler) (\ths _ st -> runnable globState ourglobs globs ths st)
+     wakeups   <- use (stateExpl.condWakeups)
+     blk       <- use (stateExpl.atomicBlock)
+     selectThreads (stateExpl.scheduler)
+       (\ths i st -> atomicAllows ths blk i && runnable globState ourglobs wakeups globs ths i st)
+
+-- | True unless another thread is running an atomic block. While a thread is
+-- in an atomic block, only it and the threads it is waiting to join (such as
+-- the flusher of a buffered store), directly or through other joins, can be
+-- scheduled.
+atomicAllows ::
+  V.Vector (ThreadState alg sym ext ret) {-^ State of all threads -} ->
+  Maybe (Int, Int) {-^ The thread in an atomic block, if any -} ->
+  Int {-^ Thread in question -} ->
+  Bool
+atomicAllows ths blk i =
+  case blk of
+    Nothing -> True
+    Just (owner, _) -> go IntSet.empty owner
+  where
+    go seen t
+      | t == i = True
+      | t `IntSet.member` seen = False
+      | otherwise =
+        case ths V.!? t of
+          Just (RunningThread (OnJoin u) _ _) -> go (IntSet.insert t seen) (threadID u)
+          _ -> False
 
 -- | Pure function that returns True if the given state denotes a runnable thread
 runnable ::
   IsSymInterface sym =>
   SymGlobalState sym {-^ Current global state -} ->
   Map.Map Text (C.Some GlobalVar) {-^ Scheduler variables that we might want to inspect -} ->
+  Map.Map Text [IntSet.IntSet] {-^ Pending notify-one wakeups for each condition variable -} ->
   [Some GlobalVar] {-^ Program globals that we might want to inspect -} ->
   V.Vector (ThreadState alg sym ext ret) {-^ State of all threads -} ->
+  Int {-^ ID of the thread in question -} ->
   ThreadState alg sym ext ret {-^ Thread in question -} ->
   Bool
-runnable st ourglobals globals allThreads ts =
+runnable st ourglobals wakeups globals allThreads tid ts =
   case ts of
     NewThread{} -> True
     EmptyThread -> False
@@ -719,13 +1015,15 @@ runnable st ourglobals globals allThreads ts =
       evalGlobalPred gv st
       | otherwise ->
         error $ "Unknown scheduler var: " ++ show predVar
-    RunningThread (OnCond _ _ notified) _ _ -> notified
+    RunningThread (OnCond cv _ notified) _ _ ->
+      notified || any (IntSet.member tid) (Map.findWithDefault [] cv wakeups)
     RunningThread (OnJoin tid) _ _
       | threadID tid < V.length allThreads ->
         done (allThreads V.! threadID tid)
       | otherwise ->
         error $ "Bad thread id " ++ show tid
     RunningThread (Resumable _) _ _ -> True
+    RunningThread OnDataAccess{} _ _ -> True
     BranchingThread {} -> True
   where
     done :: ThreadState alg sym ext ret -> Bool
@@ -12,9 +12,14 @@ import Control.Lens
 import Control.Monad.State
 import Data.Text
 import Data.Map.Strict
+import Data.IntMap.Strict (IntMap)
+import Data.IntSet (IntSet)
+import Data.Set (Set)
 import Data.Parameterized (Some(..))
 
 import Lang.Crucible.Simulator
+import Lang.Crucible.Simulator.GlobalState (SymGlobalState)
+import What4.ProgramLoc (ProgramLoc)
 
 import Crucibles.Scheduler
 import Crucibles.Execution
@@ -48,6 +53,36 @@ data Exploration alg ext ret sym = Exploration
     -- ^ Number of executions explored
   , _gVars     :: !(Map Text (Some GlobalVar))
     -- ^ Map from name to GlobalVars that the exploration has invented. Typically these are locks.
+  , _dataAccesses :: !(IntMap ProgramLoc)
+    -- ^ The events that are non-atomic memory accesses, with the location of
+    -- each access. Used to detect data races.
+  , _joinedThreads :: !(IntMap ThreadID)
+    -- ^ The events at which a thread finishes joining another, with the joined
+    -- thread. Used to detect data races.
+  , _releases :: !(IntMap (Set Text))
+    -- ^ The locks released by the thread of each event, after that event and
+    -- before its next one. Used to detect data races.
+  , _condWakeups :: !(Map Text [IntSet])
+    -- ^ For each condition variable, the notify-one signals in the current
+    -- execution that no waiting thread has woken up for yet, oldest first.
+    -- Each is given by the threads that were waiting when it was sent, since
+    -- only those can take it.
+  , _stepEnds :: !(IntMap ProgramLoc)
+    -- ^ For each event, the location at which its thread reached the next
+    -- scheduling point. Used to report the interleaving of a failing
+    -- execution.
+  , _atomicBlock :: !(Maybe (Int, Int))
+    -- ^ The thread running an atomic block in the current execution, if any,
+    -- and how deeply its blocks are nested. Other threads can't be scheduled
+    -- until the outermost block ends.
+  , _threadLocalVars :: ![Some GlobalVar]
+    -- ^ Globals that each thread has its own copy of. A new thread starts with
+    -- the values of the thread that ran before it, so the program should set
+    -- them when the thread starts.
+  , _threadLocals :: !(IntMap (SymGlobalState sym))
+    -- ^ The globals of each thread that isn't running, as of when it last
+    -- stopped. Its thread-local globals are restored from these when it
+    -- resumes.
   }
 makeLenses ''Exploration
 
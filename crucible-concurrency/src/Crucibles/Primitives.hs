@@ -115,6 +115,12 @@ data ExplorePrimitive p sym ext =
       !(RegValue sym ty) -- ^ The value to pass to the new thread
       !(sym -> Int -> IO (RegValue sym spawnRetTy))
 
+  -- | Ask for the ID of the running thread. This is not a scheduling point.
+  | forall retTy.
+    ThreadSelf
+      !(C.TypeRepr retTy) -- ^ Type of the returned ID
+      !(sym -> Int -> IO (RegValue sym retTy)) -- ^ How to construct the ID
+
   -- TODO: Fold these into ThreadYield?
   -- | Effectively pthread_cond_wait
   | ThreadCondWait
@@ -123,7 +129,13 @@ data ExplorePrimitive p sym ext =
 
   -- | Effectively pthread_cond_notify
   | ThreadCondSignal
-      !Text
+      !Bool -- ^ Wake every waiting thread, rather than just one
+      !Text -- ^ Condition variable
+
+  -- | Begin ('True') or end ('False') an atomic block, in which the running
+  -- thread is not preempted. Blocks nest. This is not a scheduling point.
+  | ThreadAtomic
+      !Bool
 
 
 -- | A @YieldSpec@ explains how a thread is modifying some global resource. This
@@ -134,6 +146,7 @@ data YieldSpec =
   | GlobalPred !Text -- ^ Wait for some global (boolean) variable to become True
   | Acquire   !Text -- ^ Acquire a lock
   | Release   !Text -- ^ Release a lock
+  | NonAtomic       -- ^ A non-atomic memory access, checked for data races
 
 -- | Run a list of matches in order, stopping with the first success.
 matchPrimitive ::
@@ -236,7 +249,7 @@ matchCondSignal :: IsSymInterface sym => ExplorePrimitiveMatcher p sym ext
 matchCondSignal _ nm SignalRepr cf _
   | nm == "cond_signal" =
     case effectVar cf of
-      Just cv -> pure $! ThreadCondSignal cv
+      Just cv -> pure $! ThreadCondSignal True cv
       _ -> error "cond_signal: symbolic cond/mut resource"
 matchCondSignal _ _ _ _ _ = Nothing
 
@@ -5,41 +5,25 @@ use core::ops::{Neg, Not, Add, Sub, Mul, Div, Rem, BitAnd, BitOr, BitXor, Shl, S
 
 use crate::symbolic::Symbolic;
 
-/// An unsigned integer (bitvector), whose size is indicated by the type parameter `S`.
-pub struct Bv<S: Size + ?Sized> {
+/// An unsigned integer (bitvector) of `N` bits, for any `N` of at least 1.  Arithmetic wraps
+/// around at `2^N`.
+pub struct Bv<const N: usize> {
     _dummy: u8,
-    _marker: PhantomData<S>,
-}
-
-pub trait Size {
-    fn make_symbolic(desc: &'static str) -> Bv<Self>;
-}
-
-pub struct _128;
-impl Size for _128 {
-    fn make_symbolic(desc: &'static str) -> Bv<Self> { make_symbolic_128(desc) }
-}
-
-pub struct _256;
-impl Size for _256 {
-    fn make_symbolic(desc: &'static str) -> Bv<Self> { make_symbolic_256(desc) }
-}
-
-pub struct _512;
-impl Size for _512 {
-    fn make_symbolic(desc: &'static str) -> Bv<Self> { make_symbolic_512(desc) }
+    // Constant type arguments are erased in the MIR that crux-mir reads, so it gets the width from
+    // the type of this field instead.
+    _marker: PhantomData<[(); N]>,
 }
 
 
 /// An unsigned 128-bit integer.
-pub type Bv128 = Bv<_128>;
+pub type Bv128 = Bv<128>;
 /// An unsigned 256-bit integer.
-pub type Bv256 = Bv<_256>;
+pub type Bv256 = Bv<256>;
 /// An unsigned 512-bit integer.
-pub type Bv512 = Bv<_512>;
+pub type Bv512 = Bv<512>;
 
 
-impl<S: Size> Bv<S> {
+impl<const N: usize> Bv<N> {
     // Defining overrides for constants is tricky: rustc will const-evaluate based on the
     // definition, and crux-mir will only see the actual struct literal, not the name of the
     // constant.  Here we handle it by setting a different value for `_dummy` in each constant, so
@@ -63,7 +47,7 @@ pub fn convert<T, U>(x: T) -> U {
 }
 
 macro_rules! impl_from_bv {
-    ($($S1:ty, $S2:ty;)*) => {
+    ($($S1:literal, $S2:literal;)*) => {
         $(
             impl From<Bv<$S1>> for Bv<$S2> {
                 fn from(x: Bv<$S1>) -> Bv<$S2> {
@@ -75,19 +59,44 @@ macro_rules! impl_from_bv {
 }
 
 impl_from_bv! {
-    _128, _256;
-    _128, _512;
-    _256, _128;
-    _256, _512;
-    _512, _128;
-    _512, _256;
+    128, 256;
+    128, 512;
+    256, 128;
+    256, 512;
+    512, 128;
+    512, 256;
+}
+
+impl<const N: usize> Bv<N> {
+    /// The same value, with `M - N` zero bits added above it.  This requires `M >= N`.
+    pub fn zero_extend<const M: usize>(self) -> Bv<M> {
+        assert!(M >= N, "zero_extend to a narrower width");
+        convert(self)
+    }
+
+    /// The low `M` bits of the value.  This requires `M <= N`.
+    pub fn truncate<const M: usize>(self) -> Bv<M> {
+        assert!(M <= N, "truncate to a wider width");
+        convert(self)
+    }
+
+    /// The value `x` modulo `2^N`.
+    pub fn from_u64(x: u64) -> Self {
+        convert(x)
+    }
+
+    /// The value as a `u64`.  This requires `N <= 64`, so that no bits are lost.
+    pub fn to_u64(self) -> u64 {
+        assert!(N <= 64, "Bv<N>::to_u64 requires N <= 64");
+        convert(self)
+    }
 }
 
 macro_rules! impl_into_prim {
     ($($T:ty),*) => {
         $(
-            impl<S: Size> From<Bv<S>> for $T {
-                fn from(x: Bv<S>) -> $T {
+            impl<const N: usize> From<Bv<N>> for $T {
+                fn from(x: Bv<N>) -> $T {
                     convert(x)
                 }
             }
@@ -98,8 +107,8 @@ macro_rules! impl_into_prim {
 macro_rules! impl_from_into_prim {
     ($($T:ty),*) => {
         $(
-            impl<S: Size> From<$T> for Bv<S> {
-                fn from(x: $T) -> Bv<S> {
+            impl<const N: usize> From<$T> for Bv<N> {
+                fn from(x: $T) -> Bv<N> {
                     convert(x)
                 }
             }
@@ -111,8 +120,8 @@ macro_rules! impl_from_into_prim {
 macro_rules! impl_from_into_prim_signed {
     ($($T:ty),*) => {
         $(
-            impl<S: Size> From<$T> for Bv<S> {
-                fn from(x: $T) -> Bv<S> {
+            impl<const N: usize> From<$T> for Bv<N> {
+                fn from(x: $T) -> Bv<N> {
                     assert!(x >= 0, "can't convert negative integer to unsigned bitvector");
                     convert(x)
                 }
@@ -129,9 +138,9 @@ impl_from_into_prim_signed!(i8, i16, i32, i64, i128, isize);
 macro_rules! impl_unops {
     ($($Op:ident, $op:ident;)*) => {
         $(
-            impl<S: Size> $Op for Bv<S> {
-                type Output = Bv<S>;
-                fn $op(self) -> Bv<S> {
+            impl<const N: usize> $Op for Bv<N> {
+                type Output = Bv<N>;
+                fn $op(self) -> Bv<N> {
                     unimplemented!()
                 }
             }
@@ -142,9 +151,9 @@ macro_rules! impl_unops {
 macro_rules! impl_binops {
     ($($Op:ident, $op:ident;)*) => {
         $(
-            impl<S: Size> $Op<Bv<S>> for Bv<S> {
-                type Output = Bv<S>;
-                fn $op(self, other: Bv<S>) -> Bv<S> {
+            impl<const N: usize> $Op<Bv<N>> for Bv<N> {
+                type Output = Bv<N>;
+                fn $op(self, other: Bv<N>) -> Bv<N> {
                     unimplemented!()
                 }
             }
@@ -158,13 +167,13 @@ macro_rules! impl_shift_ops {
             // Crucible shift ops require the shift amount and value use the same bitvector width,
             // so we convert `usize` to the right `Bv` type before calling the real shift function.
 
-            fn $op<S: Size>(x: Bv<S>, y: Bv<S>) -> Bv<S> {
+            fn $op<const N: usize>(x: Bv<N>, y: Bv<N>) -> Bv<N> {
                 unimplemented!()
             }
 
-            impl<S: Size> $Op<usize> for Bv<S> {
-                type Output = Bv<S>;
-                fn $op(self, shift: usize) -> Bv<S> {
+            impl<const N: usize> $Op<usize> for Bv<N> {
+                type Output = Bv<N>;
+                fn $op(self, shift: usize) -> Bv<N> {
                     $op(self, shift.into())
                 }
             }
@@ -193,16 +202,16 @@ impl_shift_ops! {
 }
 
 
-impl<S: Size> Bv<S> {
-    pub fn overflowing_add(self, other: Bv<S>) -> (Bv<S>, bool) {
+impl<const N: usize> Bv<N> {
+    pub fn overflowing_add(self, other: Bv<N>) -> (Bv<N>, bool) {
         unimplemented!()
     }
 
-    pub fn overflowing_sub(self, other: Bv<S>) -> (Bv<S>, bool) {
+    pub fn overflowing_sub(self, other: Bv<N>) -> (Bv<N>, bool) {
         unimplemented!()
     }
 
-    pub fn overflowing_mul(self, other: Bv<S>) -> (Bv<S>, bool) {
+    pub fn overflowing_mul(self, other: Bv<N>) -> (Bv<N>, bool) {
         unimplemented!()
     }
 
@@ -212,47 +221,45 @@ impl<S: Size> Bv<S> {
 }
 
 
-impl<S: Size> Clone for Bv<S> {
-    fn clone(&self) -> Bv<S> {
+impl<const N: usize> Clone for Bv<N> {
+    fn clone(&self) -> Bv<N> {
         *self
     }
 }
 
-impl<S: Size> Copy for Bv<S> {}
+impl<const N: usize> Copy for Bv<N> {}
 
-impl<S: Size> PartialEq<Bv<S>> for Bv<S> {
-    fn eq(&self, other: &Bv<S>) -> bool {
+impl<const N: usize> PartialEq<Bv<N>> for Bv<N> {
+    fn eq(&self, other: &Bv<N>) -> bool {
         unimplemented!()
     }
 }
 
-impl<S: Size> Eq for Bv<S> {}
+impl<const N: usize> Eq for Bv<N> {}
 
-impl<S: Size> PartialOrd<Bv<S>> for Bv<S> {
-    fn partial_cmp(&self, other: &Bv<S>) -> Option<Ordering> {
+impl<const N: usize> PartialOrd<Bv<N>> for Bv<N> {
+    fn partial_cmp(&self, other: &Bv<N>) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 
-    fn lt(&self, other: &Bv<S>) -> bool {
+    fn lt(&self, other: &Bv<N>) -> bool {
         unimplemented!()
     }
 }
 
-impl<S: Size> Ord for Bv<S> {
-    fn cmp(&self, other: &Bv<S>) -> Ordering {
+impl<const N: usize> Ord for Bv<N> {
+    fn cmp(&self, other: &Bv<N>) -> Ordering {
         if self.eq(other) { Ordering::Equal }
         else if self.lt(other) { Ordering::Less }
         else { Ordering::Greater }
     }
 }
 
-impl<S: Size> Symbolic for Bv<S> {
-    fn symbolic(desc: &'static str) -> Bv<S> {
-        S::make_symbolic(desc)
+impl<const N: usize> Symbolic for Bv<N> {
+    fn symbolic(desc: &'static str) -> Bv<N> {
+        make_symbolic(desc)
     }
 }
 
-// Override hooks for constructing symbolic bitvectors.
-fn make_symbolic_128(desc: &'static str) -> Bv<_128> { unimplemented!() }
-fn make_symbolic_256(desc: &'static str) -> Bv<_256> { unimplemented!() }
-fn make_symbolic_512(desc: &'static str) -> Bv<_512> { unimplemented!() }
+// Override hook for constructing symbolic bitvectors.
+fn make_symbolic<const N: usize>(desc: &'static str) -> Bv<N> { unimplemented!() }
@@ -2,10 +2,14 @@
 #![feature(core_intrinsics)]
 #![feature(crucible_intrinsics)]
 #![feature(unboxed_closures)]
+#![feature(const_generics)]
+#![allow(incomplete_features)]
 
 pub mod bitvector;
 pub mod cryptol;
 pub mod method_spec;
+pub mod sched;
+pub mod stats;
 pub mod sym_bytes;
 pub mod symbolic;
 
@@ -17,8 +21,9 @@ pub mod symbolic;
 #[doc(hidden)] pub use core::crucible::ptr;
 #[doc(hidden)] pub mod vector;
 
-// Re-export the `Symbolic` trait, which is used to create symbolic values.
-pub use self::symbolic::Symbolic;
+// Re-export the `Symbolic` trait and related helpers, which are used to create symbolic values.
+pub use self::symbolic::{Symbolic, SymbolicInt, concrete, nondet_in_range, symbolic_option, symbolic_result};
+pub use self::stats::{PathStats, branches_explored, path_stats};
 
 /// Assert that a condition holds.  During symbolic testing, `crux-mir` will search for an
 /// assignment to the symbolic variables that violates an assertion.
@@ -68,6 +73,10 @@ pub fn crucible_assert_impl(
 
 /// Assume that a condition holds.  `crux-mir` will not consider assignments to the symbolic
 /// variables that violate an assumption.
+///
+/// Paths on which the condition is false are discarded without reporting a failure, so an
+/// assumption that can never hold makes every later assertion pass vacuously.  `crux-mir` prints a
+/// warning when an assumption can't hold on the current path.
 #[macro_export]
 macro_rules! crucible_assume {
     ($e:expr) => {
@@ -90,11 +99,12 @@ pub fn crucible_assume_impl(
 
 
 /// Assert that the current code is unreachable.  This is similar to the standard `unreachable!()`
-/// macro, but uses `crucible_assert!` internally.
+/// macro, but uses `crucible_assert!` internally, so reaching it on any feasible path is reported
+/// as a failure ("reachable code marked unreachable").
 #[macro_export]
 macro_rules! crucible_assert_unreachable {
     () => {{
-        $crate::crucible_assert!(false);
+        $crate::crucible_assert!(false, "reachable code marked unreachable");
         unreachable!()
     }};
 }
@@ -104,11 +114,25 @@ macro_rules! crucible_assert_unreachable {
 #[macro_export]
 macro_rules! crucible_assume_unreachable {
     () => {{
-        $crate::crucible_assume!(false);
+        $crate::crucible_assume_unreachable_impl(false, "false", file!(), line!(), column!());
         unreachable!()
     }};
 }
 
+/// Internal implementation detail of `crucible_assume_unreachable!`.  This is the same as
+/// `crucible_assume_impl`, except that `crux-mir` doesn't warn that the assumption can't hold,
+/// since that's the point.
+#[doc(hidden)]
+pub fn crucible_assume_unreachable_impl(
+    _cond: bool,
+    _cond_str: &'static str,
+    _file: &'static str,
+    _line: u32,
+    _col: u32,
+) -> () {
+    ()
+}
+
 
 /// Given a symbolic value, choose an arbitrary instance that satisfies the current path condition.
 /// This function operates recursively: a call to `concretize(&(x, y))` (where `x` and `y` are
@@ -133,6 +157,28 @@ pub fn override_<F, G>(f: F, g: G) {
 pub fn dump_what4<T>(desc: &str, x: T) {
 }
 
+/// Limit every loop in the calling function to `n` iterations.  A loop that would run more than
+/// `n` times fails with "reached maximum number of loop iterations", reported at the location of
+/// that loop, instead of being explored forever.
+///
+/// This is a marker rather than an attribute (such as `#[crux::unroll(n)]`), since `mir-json` does
+/// not record attributes.  crux-mir looks for calls to `unroll` when it loads the program, so `n`
+/// must be a literal, and the call may appear anywhere in the function.  The limit applies only
+/// to the calling function, not to the functions it calls.
+pub fn unroll(n: u32) {
+}
+
+/// Limit the interleavings explored by a `--concurrency` test to those with at most `n`
+/// preemptions, that is, switches away from a thread that could have kept running.  Switches at
+/// a thread that blocks or finishes are not counted.  Most concurrency bugs need only a few
+/// preemptions to show up, and bounding them greatly reduces the number of interleavings to
+/// explore for tests with more than a couple of threads.  `n = 0` means no bound.
+///
+/// Like `unroll`, this is a marker: `n` must be a literal, and the call must appear directly in
+/// the test function.  It has no effect outside `--concurrency` mode.
+pub fn max_preemptions(n: u32) {
+}
+
 // Some older test cases still use these functions.
 #[deprecated(note = "call i8::symbolic instead")]
 pub fn crucible_i8(name: &'static str) -> i8 { Symbolic::symbolic(name) }
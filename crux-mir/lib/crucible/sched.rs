@@ -0,0 +1,44 @@
+//! Scheduling points for synchronization primitives that `--concurrency` mode doesn't model.
+//!
+//! `--concurrency` mode switches threads only at operations it knows about, such as atomic
+//! accesses, locks, and joins.  Code between two such operations runs without interruption, so
+//! a primitive built from plain memory accesses (through `UnsafeCell`, say) would never be
+//! interrupted in the middle.  `yield_point` marks a place where another thread may run, and
+//! `atomic_block` marks a region where none may.  Outside `--concurrency` mode, both have no
+//! effect.
+
+use core::crucible::concurrency;
+
+/// The resource that every `yield_point` touches, so that the scheduler considers the steps
+/// between any two yield points in different threads in both orders.
+static YIELD_POINT: u8 = 0;
+
+/// Let the scheduler switch to another thread here.  Every interleaving of the steps that
+/// threads take between yield points, and between the operations the scheduler already knows
+/// about, is explored.
+pub fn yield_point() {
+    concurrency::sched_yield(false, &YIELD_POINT as *const u8);
+}
+
+/// Run `f` without switching to another thread, and return its result.  Atomic operations, locks,
+/// and yield points inside `f` still happen, but no other thread runs until `f` returns.  Calls
+/// may be nested.
+///
+/// The only threads that can run during `f` are those the current thread waits to join.  If `f`
+/// blocks on anything else, such as a `Mutex` held by another thread, no thread can make
+/// progress, and the execution is reported as a deadlock.  If `f` panics, the block ends as the
+/// panic unwinds.
+pub fn atomic_block<R, F: FnOnce() -> R>(f: F) -> R {
+    /// Ends the block when dropped, including while unwinding.
+    struct End;
+
+    impl Drop for End {
+        fn drop(&mut self) {
+            concurrency::atomic_end();
+        }
+    }
+
+    concurrency::atomic_begin();
+    let _end = End;
+    f()
+}
@@ -0,0 +1,63 @@
+//! Statistics about the symbolic execution of the current test, for finding out where paths
+//! multiply.  These are for debugging and tuning a test; the values don't affect verification.
+//!
+//! Paths that merge back together count as one, so `paths` only grows when a path ends early, by
+//! aborting.  Memory is counted in allocations, since the size of an allocation may be symbolic.
+
+/// Counts describing the symbolic execution of the current test so far, as returned by
+/// `path_stats`.  Print it with `println!("{:?}", crucible::path_stats())`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PathStats {
+    /// The number of paths explored so far: the current one, plus every path that has ended by
+    /// aborting, such as on a panic or an assumption that can't hold.
+    pub paths: usize,
+    /// The number of branches on a symbolic condition reached so far, on any path.
+    pub branches: usize,
+    /// The number of assumptions on the current path, including the conditions of the branches
+    /// taken to reach it.
+    pub assumes: usize,
+    /// The number of assertions made so far, on any path, that will be proved at the end of the
+    /// test.
+    pub asserts: usize,
+    /// The number of heap allocations made so far on the current path, such as by `Box::new` or a
+    /// `Vec` growing.  Reallocating in place isn't counted.
+    pub allocations: usize,
+}
+
+/// Return the statistics for the current test so far.
+pub fn path_stats() -> PathStats {
+    PathStats {
+        paths: paths_aborted() + 1,
+        branches: branches_explored(),
+        assumes: assumption_count(),
+        asserts: assertion_count(),
+        allocations: allocation_count(),
+    }
+}
+
+/// Return the number of branches on a symbolic condition reached so far in the current test, on
+/// any path.  In `--concurrency` mode, this includes the branches of every execution explored so
+/// far.
+pub fn branches_explored() -> usize {
+    0
+}
+
+/// Hook for a crucible override that counts the paths that have aborted so far, on any path.
+fn paths_aborted() -> usize {
+    0
+}
+
+/// Hook for a crucible override that counts the assumptions on the current path.
+fn assumption_count() -> usize {
+    0
+}
+
+/// Hook for a crucible override that counts the assertions made so far.
+fn assertion_count() -> usize {
+    0
+}
+
+/// Hook for a crucible override that counts the heap allocations on the current path.
+fn allocation_count() -> usize {
+    0
+}
@@ -1,3 +1,9 @@
+use core::mem::MaybeUninit;
+
+/// `#[derive(Symbolic)]` implements `Symbolic` for a struct or enum whose fields all implement
+/// `Symbolic`.  For an enum, the variant is symbolic too.
+pub use crucible_derive::Symbolic;
+
 pub trait Symbolic: Sized {
     /// Create a new symbolic value of this type.  `desc` is used to refer to this symbolic value
     /// when printing counterexamples.
@@ -75,45 +81,105 @@ int_impls! {
     isize, usize;
 }
 
-impl Symbolic for bool {
-    fn symbolic(desc: &'static str) -> bool {
-        let val = u8::symbolic_where(desc, |&x| x < 2);
-        val == 1
-    }
+/// The integer types, which are the types `nondet_in_range` accepts.
+pub trait SymbolicInt: Symbolic + PartialOrd {}
+
+macro_rules! symbolic_int_impls {
+    ($($ty:ty),*) => {
+        $( impl SymbolicInt for $ty {} )*
+    };
 }
 
+symbolic_int_impls!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Extra constructors for symbolic floating-point values.
+pub trait SymbolicFloat: Symbolic {
+    /// Create a new symbolic value of this type that is not NaN.  It may still be infinite.
+    fn symbolic_nonnan(desc: &'static str) -> Self;
+}
 
-macro_rules! array_impls {
-    ($($size:expr)*) => {
+macro_rules! float_impls {
+    ($($ty:ty, $func:ident;)*) => {
         $(
-            impl<T: Symbolic + Copy> Symbolic for [T; $size] {
-                fn symbolic(desc: &'static str) -> [T; $size] {
-                    let mut arr = [T::symbolic(desc); $size];
-                    for i in 1 .. $size {
-                        arr[i] = T::symbolic(desc);
-                    }
-                    arr
+            /// Hook for a crucible override that creates a symbolic instance of $ty.
+            #[allow(unused)]
+            fn $func(desc: &'static str) -> $ty { unimplemented!(stringify!($func)); }
+
+            /// The result can be any value of the type, including NaN and the infinities.  Use
+            /// `SymbolicFloat::symbolic_nonnan` to exclude NaN.
+            impl Symbolic for $ty {
+                fn symbolic(desc: &'static str) -> $ty { $func(desc) }
+            }
+
+            impl SymbolicFloat for $ty {
+                fn symbolic_nonnan(desc: &'static str) -> $ty {
+                    Self::symbolic_where(desc, |x| !x.is_nan())
                 }
             }
         )*
     };
 }
 
-array_impls! {
-    0 1 2 3 4 5 6 7 8 9
-    10 11 12 13 14 15 16 17 18 19
-    20 21 22 23 24 25 26 27 28 29
-    30 31 32
+float_impls! {
+    f32, symbolic_f32;
+    f64, symbolic_f64;
+}
+
+impl Symbolic for bool {
+    fn symbolic(desc: &'static str) -> bool {
+        let val = u8::symbolic_where(desc, |&x| x < 2);
+        val == 1
+    }
+}
+
+/// The result is always a valid Unicode scalar value: surrogates (`0xD800 ..= 0xDFFF`) and values
+/// above `char::MAX` are never produced.
+impl Symbolic for char {
+    fn symbolic(desc: &'static str) -> char {
+        let val = u32::symbolic_where(desc, |&x| x < 0xD800 || (0xE000 <= x && x <= 0x10FFFF));
+        unsafe { core::char::from_u32_unchecked(val) }
+    }
 }
 
+
+/// Hook for a crucible override that returns the name `desc[index]`, used to name the elements
+/// of symbolic arrays.
+#[allow(unused)]
+fn indexed_name(desc: &'static str, index: usize) -> &'static str { desc }
+
+/// Hook for a crucible override that returns the name `desc.index`, used to name the components
+/// of symbolic tuples.
+#[allow(unused)]
+fn field_name(desc: &'static str, index: usize) -> &'static str { desc }
+
+/// Each element is a separate symbolic value.  In counterexamples, the elements are named
+/// `desc[0]`, `desc[1]`, and so on.
+impl<T: Symbolic, const N: usize> Symbolic for [T; N] {
+    fn symbolic(desc: &'static str) -> [T; N] {
+        let mut arr = MaybeUninit::<[T; N]>::uninit();
+        let ptr = arr.as_mut_ptr() as *mut T;
+        for i in 0 .. N {
+            unsafe { ptr.add(i).write(T::symbolic(indexed_name(desc, i))) };
+        }
+        unsafe { arr.assume_init() }
+    }
+}
+
+/// Each component is a separate symbolic value.  In counterexamples, the components are named
+/// `desc.0`, `desc.1`, and so on.
 macro_rules! tuple_impls {
     ($($($name:ident)*;)*) => {
         $(
             #[allow(unused)] #[allow(bad_style)]
             impl<$($name: Symbolic,)*> Symbolic for ($($name,)*) {
                 fn symbolic(desc: &'static str) -> ($($name,)*) {
+                    let mut i = 0;
                     (
-                        $($name::symbolic(desc),)*
+                        $({
+                            let x = $name::symbolic(field_name(desc, i));
+                            i += 1;
+                            x
+                        },)*
                     )
                 }
             }
@@ -137,6 +203,79 @@ tuple_impls! {
     A B C D E F G H I J K L;
 }
 
+/// The result is `Some` or `None` depending on a symbolic condition, so code that matches on it
+/// explores both cases.
+impl<T: Symbolic> Symbolic for Option<T> {
+    fn symbolic(desc: &'static str) -> Option<T> {
+        if bool::symbolic(desc) { Some(T::symbolic(desc)) } else { None }
+    }
+}
+
+/// The result is `Ok` or `Err` depending on a symbolic condition, so code that matches on it
+/// explores both cases.
+impl<T: Symbolic, E: Symbolic> Symbolic for Result<T, E> {
+    fn symbolic(desc: &'static str) -> Result<T, E> {
+        if bool::symbolic(desc) { Ok(T::symbolic(desc)) } else { Err(E::symbolic(desc)) }
+    }
+}
+
+/// Create a symbolic `Option<T>`, which may be `None` or `Some` of a symbolic `T`.
+pub fn symbolic_option<T: Symbolic>(desc: &'static str) -> Option<T> {
+    Option::symbolic(desc)
+}
+
+/// Create a symbolic `Result<T, E>`, which may be `Ok` of a symbolic `T` or `Err` of a symbolic
+/// `E`.
+pub fn symbolic_result<T: Symbolic, E: Symbolic>(desc: &'static str) -> Result<T, E> {
+    Result::symbolic(desc)
+}
+
+fn assert_range_nonempty(nonempty: bool) {
+    super::crucible_assert!(nonempty, "nondet_in_range: the range is empty");
+}
+
+/// Create a symbolic integer in the range `lo .. hi`, such as an index into a collection of
+/// length `hi`.  It's an error for the range to be empty: if `lo` might not be less than `hi`, the
+/// call fails with an assertion error.
+pub fn nondet_in_range<T: SymbolicInt>(desc: &'static str, lo: T, hi: T) -> T {
+    assert_range_nonempty(lo < hi);
+    T::symbolic_where(desc, |x| lo <= *x && *x < hi)
+}
+
+
+/// Types whose symbolic values can be pinned with `concrete`.  These are the types whose
+/// `Symbolic` instance creates a single bitvector variable.
+pub trait Concrete: Symbolic {
+    /// The bits of `self`, as stored in the underlying bitvector variable.  Signed values are
+    /// sign-extended; the override truncates them to the variable's width.
+    fn to_bits(self) -> u128;
+}
+
+macro_rules! concrete_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl Concrete for $ty {
+                fn to_bits(self) -> u128 { self as u128 }
+            }
+        )*
+    };
+}
+
+concrete_impls!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, char);
+
+/// Hook for a crucible override that assumes the symbolic variable named `desc` has the value
+/// `bits`.
+#[allow(unused)]
+fn concrete_bits(desc: &'static str, bits: u128) {}
+
+/// Pin the symbolic variable named `desc`, which must already have been created, to `value`.
+/// After this, the test explores only the paths on which the variable has that value, so pinning
+/// the values from a counterexample turns a failing symbolic test into a deterministic
+/// reproduction of the failure.  It's an error if no variable, or more than one, has this name.
+pub fn concrete<T: Concrete>(desc: &'static str, value: T) {
+    concrete_bits(desc, value.to_bits())
+}
+
 
 /// Take a symbolic-length prefix of `xs`.  The length of the returned slice can be anywhere in the
 /// range `0 ..= xs.len()`.
@@ -0,0 +1,265 @@
+//! `#[derive(Symbolic)]`, re-exported from the `crucible` crate.
+//!
+//! This crate is compiled natively, since it runs inside the compiler.  It avoids depending on
+//! `syn` and `quote`, so it does its own parsing of the item, which is limited to what the derive
+//! needs: the item's name and generics, and the names or number of its fields.
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+
+/// Derive `crucible::Symbolic` for a struct or enum.
+///
+/// For a struct, each field is a fresh symbolic value.  For an enum, the variant is chosen by a
+/// fresh symbolic value, so code that matches on the result explores every variant, and each
+/// field of the variant is a fresh symbolic value.  All fields use the `desc` of the whole value.
+/// Each type parameter of the item must implement `Symbolic`.
+#[proc_macro_derive(Symbolic)]
+pub fn derive_symbolic(input: TokenStream) -> TokenStream {
+    let code = match expand(input) {
+        Ok(code) => code,
+        Err(msg) => format!("compile_error!({:?});", msg),
+    };
+    code.parse().unwrap()
+}
+
+struct Generics {
+    /// Parameters for the `impl`, without defaults.
+    params: Vec<String>,
+    /// Arguments for the type, which are the names of the parameters.
+    args: Vec<String>,
+    /// Names of the type parameters, which get a `Symbolic` bound.
+    ty_params: Vec<String>,
+    /// Predicates from the item's `where` clause.
+    preds: Vec<String>,
+}
+
+enum Fields {
+    Named(Vec<String>),
+    Unnamed(usize),
+    Unit,
+}
+
+fn expand(input: TokenStream) -> Result<String, String> {
+    let tokens = input.into_iter().collect::<Vec<_>>();
+    let mut i = 0;
+    skip_attrs_and_vis(&tokens, &mut i);
+    let kind = ident_at(&tokens, i).ok_or("expected `struct` or `enum`")?;
+    let name = ident_at(&tokens, i + 1).ok_or("expected a type name")?;
+    i += 2;
+    let mut generics = parse_generics(&tokens, &mut i);
+    generics.preds = parse_where(&tokens, &mut i);
+
+    let body = match &kind[..] {
+        "struct" => {
+            let fields = parse_fields(tokens.get(i));
+            if let Fields::Unnamed(_) = fields {
+                // The `where` clause of a tuple struct comes after its fields.
+                i += 1;
+                generics.preds = parse_where(&tokens, &mut i);
+            }
+            construct("Self", &fields)
+        },
+        "enum" => {
+            let variants = match tokens.get(i) {
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace =>
+                    parse_variants(g.stream()),
+                _ => return Err("expected enum variants".into()),
+            };
+            match variants.len() {
+                0 => return Err("`Symbolic` can't be derived for an enum with no variants".into()),
+                1 => construct(&format!("Self::{}", variants[0].0), &variants[0].1),
+                n => {
+                    let arms = variants.iter().enumerate().map(|(k, (v, fields))| {
+                        let pat = if k == n - 1 { "_".to_owned() } else { k.to_string() };
+                        format!("{} => {},", pat, construct(&format!("Self::{}", v), fields))
+                    }).collect::<String>();
+                    format!(
+                        "match <u32 as ::crucible::Symbolic>::symbolic_where(desc, |&x| x < {}) {{ {} }}",
+                        n, arms,
+                    )
+                },
+            }
+        },
+        _ => return Err("`Symbolic` can only be derived for structs and enums".into()),
+    };
+
+    let mut preds = generics.preds;
+    preds.extend(generics.ty_params.iter().map(|t| format!("{}: ::crucible::Symbolic", t)));
+    Ok(format!(
+        "impl<{}> ::crucible::Symbolic for {}<{}> where {} {{
+            fn symbolic(desc: &'static str) -> Self {{ {} }}
+        }}",
+        generics.params.join(", "), name, generics.args.join(", "), preds.join(", "), body,
+    ))
+}
+
+/// Build an expression that constructs `path` with a fresh symbolic value for each field.
+fn construct(path: &str, fields: &Fields) -> String {
+    let sym = "::crucible::Symbolic::symbolic(desc)";
+    match fields {
+        Fields::Named(names) => {
+            let inits = names.iter().map(|n| format!("{}: {}", n, sym)).collect::<Vec<_>>();
+            format!("{} {{ {} }}", path, inits.join(", "))
+        },
+        Fields::Unnamed(n) => format!("{}({})", path, vec![sym; *n].join(", ")),
+        Fields::Unit => path.to_owned(),
+    }
+}
+
+fn ident_at(tokens: &[TokenTree], i: usize) -> Option<String> {
+    match tokens.get(i) {
+        Some(TokenTree::Ident(id)) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+fn is_punct(token: Option<&TokenTree>, c: char) -> bool {
+    match token {
+        Some(TokenTree::Punct(p)) => p.as_char() == c,
+        _ => false,
+    }
+}
+
+fn to_string(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+/// Skip attributes (including doc comments) and a visibility qualifier.
+fn skip_attrs_and_vis(tokens: &[TokenTree], i: &mut usize) {
+    loop {
+        match (tokens.get(*i), tokens.get(*i + 1)) {
+            (Some(TokenTree::Punct(p)), Some(TokenTree::Group(g)))
+                if p.as_char() == '#' && g.delimiter() == Delimiter::Bracket => *i += 2,
+            (Some(TokenTree::Ident(id)), next) if id.to_string() == "pub" => {
+                *i += 1;
+                if let Some(TokenTree::Group(g)) = next {
+                    if g.delimiter() == Delimiter::Parenthesis {
+                        *i += 1;
+                    }
+                }
+            },
+            _ => return,
+        }
+    }
+}
+
+/// Split `tokens` at commas that aren't nested inside `<...>`, dropping empty pieces.
+fn split_commas(tokens: &[TokenTree]) -> Vec<&[TokenTree]> {
+    let mut pieces = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, tok) in tokens.iter().enumerate() {
+        if let TokenTree::Punct(p) = tok {
+            match p.as_char() {
+                '<' => depth += 1,
+                // The `>` of `->` doesn't close anything.
+                '>' if !(i > 0 && is_joint_minus(&tokens[i - 1])) => depth -= 1,
+                ',' if depth == 0 => {
+                    pieces.push(&tokens[start..i]);
+                    start = i + 1;
+                },
+                _ => {},
+            }
+        }
+    }
+    pieces.push(&tokens[start..]);
+    pieces.retain(|p| !p.is_empty());
+    pieces
+}
+
+fn is_joint_minus(tok: &TokenTree) -> bool {
+    match tok {
+        TokenTree::Punct(p) => p.as_char() == '-' && p.spacing() == Spacing::Joint,
+        _ => false,
+    }
+}
+
+/// Parse the generic parameters of the item, if there are any.
+fn parse_generics(tokens: &[TokenTree], i: &mut usize) -> Generics {
+    let mut generics = Generics {
+        params: Vec::new(),
+        args: Vec::new(),
+        ty_params: Vec::new(),
+        preds: Vec::new(),
+    };
+    if !is_punct(tokens.get(*i), '<') {
+        return generics;
+    }
+
+    let start = *i + 1;
+    let mut depth = 1;
+    *i += 1;
+    while depth > 0 && *i < tokens.len() {
+        if is_punct(tokens.get(*i), '<') {
+            depth += 1;
+        } else if is_punct(tokens.get(*i), '>') && !is_joint_minus(&tokens[*i - 1]) {
+            depth -= 1;
+        }
+        *i += 1;
+    }
+
+    for param in split_commas(&tokens[start .. *i - 1]) {
+        // Defaults are only allowed on the item, not on the `impl`.
+        let end = param.iter().position(|t| is_punct(Some(t), '=')).unwrap_or(param.len());
+        generics.params.push(to_string(&param[..end]));
+        if is_punct(param.first(), '\'') {
+            generics.args.push(to_string(&param[..2]));
+        } else if ident_at(param, 0).as_ref().map(|s| &s[..]) == Some("const") {
+            generics.args.push(to_string(&param[1..2]));
+        } else {
+            let name = to_string(&param[..1]);
+            generics.args.push(name.clone());
+            generics.ty_params.push(name);
+        }
+    }
+    generics
+}
+
+/// Parse a `where` clause, if there is one, returning its predicates.
+fn parse_where(tokens: &[TokenTree], i: &mut usize) -> Vec<String> {
+    if ident_at(tokens, *i).as_ref().map(|s| &s[..]) != Some("where") {
+        return Vec::new();
+    }
+    let start = *i + 1;
+    *i += 1;
+    while *i < tokens.len() {
+        match &tokens[*i] {
+            TokenTree::Group(g) if g.delimiter() == Delimiter::Brace => break,
+            TokenTree::Punct(p) if p.as_char() == ';' => break,
+            _ => *i += 1,
+        }
+    }
+    split_commas(&tokens[start .. *i]).into_iter().map(to_string).collect()
+}
+
+fn parse_fields(token: Option<&TokenTree>) -> Fields {
+    match token {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+            let tokens = g.stream().into_iter().collect::<Vec<_>>();
+            let names = split_commas(&tokens).into_iter().filter_map(|field| {
+                let mut j = 0;
+                skip_attrs_and_vis(field, &mut j);
+                ident_at(field, j)
+            }).collect();
+            Fields::Named(names)
+        },
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+            let tokens = g.stream().into_iter().collect::<Vec<_>>();
+            Fields::Unnamed(split_commas(&tokens).len())
+        },
+        _ => Fields::Unit,
+    }
+}
+
+/// Parse the variants of an enum into their names and fields.  Explicit discriminants are
+/// ignored.
+fn parse_variants(stream: TokenStream) -> Vec<(String, Fields)> {
+    let tokens = stream.into_iter().collect::<Vec<_>>();
+    split_commas(&tokens).into_iter().filter_map(|variant| {
+        let mut j = 0;
+        skip_attrs_and_vis(variant, &mut j);
+        let name = ident_at(variant, j)?;
+        Some((name, parse_fields(variant.get(j + 1))))
+    }).collect()
+}
@@ -925,20 +925,14 @@ where
     }
 }
 
-// Crux: replace merge sort implementation with a simple selection sort.  Parts of the original
-// sort implementation rely on `impl Drop`, which we don't support.
+// Crux: instead of running merge sort, use `crucible::sort::sort_by`, which runs an insertion
+// sort on concrete comparisons, and otherwise picks a symbolic permutation of `v` and assumes
+// that it is sorted.  Each element's original index is permuted along with it, so the model can also assume
+// that the sort is stable.
 fn merge_sort<T, F>(v: &mut [T], mut is_less: F)
 where
     F: FnMut(&T, &T) -> bool,
 {
-
-    for i in 0 .. v.len() {
-        let mut best_idx = i;
-        for j in i + 1 .. v.len() {
-            if is_less(&v[j], &v[best_idx]) {
-                best_idx = j;
-            }
-        }
-        v.swap(i, best_idx);
-    }
+    let mut order = (0 .. v.len()).collect::<Vec<_>>();
+    core::crucible::sort::sort_by(v, &mut is_less, Some(&mut order));
 }
@@ -2449,3 +2449,56 @@ impl DoubleEndedIterator for Drain<'_> {
 
 #[stable(feature = "fused", since = "1.26.0")]
 impl FusedIterator for Drain<'_> {}
+
+/// Create a string of symbolic length, containing symbolic UTF-8.  The length of the result in
+/// bytes can be anywhere in the range `0 ..= max_len`, and its bytes are always valid UTF-8.
+/// `desc` is used to refer to both the length and the bytes when printing counterexamples.
+///
+/// This lives here rather than in the `crucible` crate because `crucible` can't depend on `alloc`.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+#[doc(hidden)]
+pub fn symbolic_string(desc: &'static str, max_len: usize) -> String {
+    let bytes = crate::vec::symbolic_vec::<u8>(desc, max_len);
+    let mut state = 0;
+    for &b in &bytes {
+        state = utf8_step(state, b);
+    }
+    // Ending in any other state means the bytes are invalid or end partway through a character.
+    crucible::crucible_assume!(state == 0);
+    unsafe { String::from_utf8_unchecked(bytes) }
+}
+
+/// Step a UTF-8 validator that is in state `state` over the byte `b`.  State 0 is between
+/// characters, and `UTF8_INVALID` means the bytes seen so far aren't a prefix of valid UTF-8.  The
+/// other states track how many continuation bytes are left, and the range the next one must be in
+/// to rule out overlong encodings, surrogates, and values above `char::MAX`.
+fn utf8_step(state: u8, b: u8) -> u8 {
+    let cont = 0x80 <= b && b <= 0xbf;
+    match state {
+        0 => match b {
+            0x00..=0x7f => 0,
+            0xc2..=0xdf => 1,
+            0xe0 => 4,
+            0xed => 5,
+            0xe1..=0xef => 2,
+            0xf0 => 6,
+            0xf1..=0xf3 => 3,
+            0xf4 => 7,
+            _ => UTF8_INVALID,
+        },
+        1 if cont => 0,
+        2 if cont => 1,
+        3 if cont => 2,
+        // After 0xe0: the second byte must be at least 0xa0, or the encoding is overlong.
+        4 if 0xa0 <= b && b <= 0xbf => 1,
+        // After 0xed: the second byte must be below 0xa0, or the result is a surrogate.
+        5 if 0x80 <= b && b <= 0x9f => 1,
+        // After 0xf0: the second byte must be at least 0x90, or the encoding is overlong.
+        6 if 0x90 <= b && b <= 0xbf => 2,
+        // After 0xf4: the second byte must be below 0x90, or the result is above `char::MAX`.
+        7 if 0x80 <= b && b <= 0x8f => 2,
+        _ => UTF8_INVALID,
+    }
+}
+
+const UTF8_INVALID: u8 = 8;
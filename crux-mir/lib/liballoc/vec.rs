@@ -1073,27 +1073,20 @@ impl<T> Vec<T> {
     /// vec.retain(|_| (keep[i], i += 1).0);
     /// assert_eq!(vec, [2, 3, 5]);
     /// ```
+    ///
+    /// Crux: `f` is still called on each element once, in order, but unless every result is
+    /// concrete, instead of shifting the kept elements down in a loop that branches on each
+    /// result, this uses `crucible::retain::retain`, which picks a symbolic order of the elements
+    /// and assumes that the kept ones are at the front, in their original order.
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&T) -> bool,
     {
-        let len = self.len();
-        let mut del = 0;
-        {
-            let v = &mut **self;
-
-            for i in 0..len {
-                if !f(&v[i]) {
-                    del += 1;
-                } else if del > 0 {
-                    v.swap(i - del, i);
-                }
-            }
-        }
-        if del > 0 {
-            self.truncate(len - del);
-        }
+        let mut keep = self.iter().map(|x| f(x)).collect::<Vec<_>>();
+        let mut order = (0 .. self.len()).collect::<Vec<_>>();
+        let len = core::crucible::retain::retain(self.as_mut_slice(), &mut keep, &mut order);
+        self.truncate(len);
     }
 
     /// Removes all but the first of consecutive elements in the vector that resolve to the same
@@ -1138,15 +1131,20 @@ impl<T> Vec<T> {
     ///
     /// assert_eq!(vec, ["foo", "bar", "baz", "bar"]);
     /// ```
+    ///
+    /// Crux: instead of running the loop in `partition_dedup_by`, this uses
+    /// `crucible::dedup::dedup_by`, which runs the same loop while the comparisons are concrete,
+    /// and otherwise picks a symbolic order of the elements and assumes that the kept ones are at
+    /// the front.  Symbolic comparisons don't cause branching, but `same_bucket` is then called on
+    /// more pairs of elements, so it should have no side effects.
     #[stable(feature = "dedup_by", since = "1.16.0")]
-    pub fn dedup_by<F>(&mut self, same_bucket: F)
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
     where
         F: FnMut(&mut T, &mut T) -> bool,
     {
-        let len = {
-            let (dedup, _) = self.as_mut_slice().partition_dedup_by(same_bucket);
-            dedup.len()
-        };
+        let mut order = (0 .. self.len()).collect::<Vec<_>>();
+        let len =
+            core::crucible::dedup::dedup_by(self.as_mut_slice(), &mut same_bucket, &mut order);
         self.truncate(len);
     }
 
@@ -1689,6 +1687,26 @@ impl<T> Vec<T> {
     }
 }
 
+/// Create a vector of symbolic length, containing symbolic elements.  The length of the result can
+/// be anywhere in the range `0 ..= max_len`.  `desc` is used to refer to both the length and the
+/// elements when printing counterexamples.
+///
+/// This lives here rather than in the `crucible` crate because `crucible` can't depend on `alloc`.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+#[doc(hidden)]
+pub fn symbolic_vec<T: crucible::Symbolic>(desc: &'static str, max_len: usize) -> Vec<T> {
+    // Computing the length with `%` (rather than with `symbolic_where`) lets what4 bound it
+    // without consulting the solver, so loops over the vector terminate.
+    let len = usize::symbolic(desc) % (max_len + 1);
+    let mut v = Vec::with_capacity(max_len);
+    for _ in 0..max_len {
+        v.push(T::symbolic(desc));
+    }
+    // Elements past `len` are leaked, as with `mem::forget`.
+    unsafe { v.set_len(len) };
+    v
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Internal methods and functions
 ////////////////////////////////////////////////////////////////////////////////
@@ -2087,7 +2105,11 @@ where
 {
     fn spec_extend(&mut self, iterator: slice::Iter<'a, T>) {
         let slice = iterator.as_slice();
-        self.reserve(slice.len());
+        // Crux: reserving space for a slice of symbolic length would allocate a symbolic number
+        // of elements, which isn't supported.  Instead, when the length is symbolic, this
+        // reserves space for as many elements as the slice's allocation has room for, which is
+        // usually concrete.  A concrete length is reserved exactly, as before.
+        self.reserve(core::crucible::ptr::len_bound(slice.as_ptr(), slice.len()));
         unsafe {
             let len = self.len();
             self.set_len(len + slice.len());
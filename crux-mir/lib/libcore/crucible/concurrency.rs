@@ -1,5 +1,6 @@
 //! Crucible concurrency intrinsics
 
+use super::store_buffer;
 
 // Signal a possible preemption point. `ro` is true if this
 // preemption point MUST be read only. It is always safe to
@@ -17,6 +18,36 @@ pub fn mutex_lock<T>(x: *const T) {}
 #[unstable(feature = "crucible_intrinsics", issue = "none")]
 pub fn mutex_unlock<T>(x: *const T) {}
 
+// Signal that the current thread is waiting on condition variable `cv`.  The
+// caller must release mutex `m` before calling this, and reacquire it after.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn cond_wait<C, M>(cv: *const C, m: *const M) {}
+
+// Wake all threads waiting on condition variable `cv`.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn cond_signal<C>(cv: *const C) {}
+
+// Wake one of the threads waiting on condition variable `cv`, if there are any.
+// Each choice of thread is explored.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn cond_signal_one<C>(cv: *const C) {}
+
+// The ID of the running thread.  This is 0 without `--concurrency`.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn current_thread() -> u32 {
+    0
+}
+
+// Begin an atomic block: until the matching `atomic_end`, no other thread is
+// scheduled, except threads the current one waits to join.  Blocks nest.
+// Neither call is a scheduling point.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn atomic_begin() {}
+
+// End the innermost atomic block begun by `atomic_begin`.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn atomic_end() {}
+
 // Signal to block until thread `thid` has terminated.
 pub fn join<T>(thid : u64) -> T {
     panic!("crucible::concurrency::join should never be executed!")
@@ -40,13 +71,16 @@ returns. So instead we'll just explicitly call a function that indicates what
 the returned value was, from which we can yield control & mark the thread done
 (effectively never running the return handler for the thread in crucible land).
 */
-fn spawn_internal<F, T>(f: F) -> u32
+pub(crate) fn spawn_internal<F, T>(f: F) -> u32
 where
     F: FnOnce() -> T,
-    F: Send + 'static,
-    T: Send + 'static,
+    F: Send,
+    T: Send,
 {
+    super::unwind::start_thread();
     let x = f();
+    // Everything the thread did is visible to a thread that joins it.
+    store_buffer::flush_thread();
     thread_exit(x);
     0 // Will override
 }
@@ -58,5 +92,20 @@ where
     F: Send + 'static,
     T: Send + 'static,
 {
+    // Everything the parent did before the spawn is visible to the new thread.
+    store_buffer::flush_thread();
+    spawn_internal(f)
+}
+
+// Like `spawn`, but `f` may borrow from the caller.  The caller must join the
+// new thread before any of those borrows expire.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub unsafe fn spawn_scoped<F, T>(f: F) -> u32
+where
+    F: FnOnce() -> T,
+    F: Send,
+    T: Send,
+{
+    store_buffer::flush_thread();
     spawn_internal(f)
 }
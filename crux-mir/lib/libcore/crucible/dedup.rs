@@ -0,0 +1,86 @@
+//! A model of `dedup_by`, used in place of the loop in `Vec::dedup_by`.
+
+use super::symbolic::{assume_impl, bool_is_concrete, symbolic_usize};
+
+/// Move the first element of each run of elements in `v` for which `same_bucket` holds to the
+/// front of `v`, in their original order, and return the number of such elements.  The other
+/// elements are left after them in an unspecified order.  `order` must have the same length as
+/// `v` and hold `0 .. v.len()`; it's permuted along with `v`.
+///
+/// While the results of `same_bucket` are concrete, this runs the loop of `Vec::dedup_by`.  Once
+/// one is symbolic, as in `sort_by`, the elements are put in a symbolic order, and the result is
+/// described by assumptions instead of computed.  Each kept element is not in the same bucket as the kept
+/// element before it, and each dropped element is in the same bucket as the last kept element
+/// before it in the original order, so the elements kept are exactly those `dedup_by` keeps.
+/// None of this branches on the results of `same_bucket`.
+///
+/// `same_bucket` is called on more pairs of elements than `dedup_by` would call it on, so it
+/// should be a pure function of its arguments.  If it isn't consistent, the assumptions may be
+/// unsatisfiable, which makes every later assertion on the path vacuous.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn dedup_by<T, F>(v: &mut [T], same_bucket: &mut F, order: &mut [usize]) -> usize
+where
+    F: FnMut(&mut T, &mut T) -> bool,
+{
+    let len = v.len();
+    if len <= 1 {
+        return len;
+    }
+    if let Some(kept) = dedup_concrete(v, same_bucket, order) {
+        return kept;
+    }
+
+    for i in 0..len {
+        let j = symbolic_usize("dedup_index");
+        assume_impl(i <= j && j < len, "i <= j && j < len", file!(), line!(), column!());
+        v.swap(i, j);
+        order.swap(i, j);
+    }
+    let kept = symbolic_usize("dedup_len");
+    assume_impl(1 <= kept && kept <= len, "1 <= kept && kept <= len", file!(), line!(), column!());
+    assume_impl(order[0] == 0, "order[0] == 0", file!(), line!(), column!());
+
+    // The conditions below use `|` and `&` instead of `||` and `&&`, and `same_bucket` is called
+    // unconditionally, so that none of them branches.
+    let ptr = v.as_mut_ptr();
+    for i in 1..len {
+        let same = unsafe { same_bucket(&mut *ptr.add(i), &mut *ptr.add(i - 1)) };
+        assume_impl((i >= kept) | ((order[i - 1] < order[i]) & !same),
+                    "i >= kept || (order[i - 1] < order[i] && !same_bucket(v[i], v[i - 1]))",
+                    file!(), line!(), column!());
+    }
+    for d in 1..len {
+        for i in 0..d {
+            // Is `v[i]` the last kept element before the dropped element `v[d]`?
+            let last_before = (i < kept) & (kept <= d) & (order[i] < order[d])
+                & ((i + 1 >= kept) | (order[i + 1] > order[d]));
+            let same = unsafe { same_bucket(&mut *ptr.add(d), &mut *ptr.add(i)) };
+            assume_impl(!last_before | same, "same_bucket(v[d], v[i])",
+                        file!(), line!(), column!());
+        }
+    }
+    kept
+}
+
+/// Run the loop of `Vec::dedup_by` on `v`, permuting `order` along with it, and return the number
+/// of elements kept.  This stops and returns `None` at the first symbolic result of
+/// `same_bucket`, leaving `v` a permutation of the original.
+fn dedup_concrete<T, F>(v: &mut [T], same_bucket: &mut F, order: &mut [usize]) -> Option<usize>
+where
+    F: FnMut(&mut T, &mut T) -> bool,
+{
+    let mut w = 1;
+    for r in 1..v.len() {
+        let (kept, rest) = v.split_at_mut(r);
+        let same = same_bucket(&mut rest[0], &mut kept[w - 1]);
+        if !bool_is_concrete(same) {
+            return None;
+        }
+        if !same {
+            v.swap(r, w);
+            order.swap(r, w);
+            w += 1;
+        }
+    }
+    Some(w)
+}
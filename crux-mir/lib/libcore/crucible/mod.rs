@@ -8,3 +8,21 @@ pub mod ptr;
 
 #[unstable(feature = "crucible_intrinsics", issue = "none")]
 pub mod concurrency;
+
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub mod store_buffer;
+
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub mod symbolic;
+
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub mod unwind;
+
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub mod sort;
+
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub mod dedup;
+
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub mod retain;
@@ -6,3 +6,13 @@
 pub fn compare_usize<T: ?Sized>(ptr: *const T, val: usize) -> bool {
     unimplemented!("ptr::compare_usize")
 }
+
+/// A bound on the length `len` of a slice starting at `ptr`.  This is `len` itself if it's
+/// concrete or if the allocation has no fixed length, and otherwise the number of elements between
+/// `ptr` and the end of its allocation.  That is usually concrete even when `len` is symbolic, so
+/// a loop up to it, that only uses the elements below `len`, doesn't fork a path for each possible
+/// length.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn len_bound<T>(ptr: *const T, len: usize) -> usize {
+    len
+}
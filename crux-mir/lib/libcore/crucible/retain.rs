@@ -0,0 +1,48 @@
+//! A model of `retain`, used in place of the loop in `Vec::retain`.
+
+use super::symbolic::{assume_impl, bool_is_concrete, symbolic_usize};
+
+/// Move the elements `v[i]` for which `keep[i]` holds to the front of `v`, in their original
+/// order, and return the number of such elements.  The other elements are left after them in an
+/// unspecified order.  `keep` must have the same length as `v`, as must `order`, which must hold
+/// `0 .. v.len()`; the contents of both are unspecified afterward.
+///
+/// If `keep` is concrete, this shifts the kept elements down, as `Vec::retain` does.  Otherwise,
+/// as in `dedup_by`, the elements are put in a symbolic order, and the result is described by
+/// assumptions instead of computed.  Exactly the elements at the front are kept, and their
+/// original indices increase, so none of this branches on `keep`.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn retain<T>(v: &mut [T], keep: &mut [bool], order: &mut [usize]) -> usize {
+    let len = v.len();
+    if keep.iter().all(|&k| bool_is_concrete(k)) {
+        let mut del = 0;
+        for i in 0..len {
+            if !keep[i] {
+                del += 1;
+            } else if del > 0 {
+                v.swap(i - del, i);
+            }
+        }
+        return len - del;
+    }
+
+    for i in 0..len {
+        let j = symbolic_usize("retain_index");
+        assume_impl(i <= j && j < len, "i <= j && j < len", file!(), line!(), column!());
+        v.swap(i, j);
+        keep.swap(i, j);
+        order.swap(i, j);
+    }
+    let kept = symbolic_usize("retain_len");
+    assume_impl(kept <= len, "kept <= len", file!(), line!(), column!());
+
+    // `|` doesn't branch, unlike `||`.
+    for i in 0..len {
+        assume_impl((i < kept) == keep[i], "(i < kept) == keep[i]", file!(), line!(), column!());
+    }
+    for i in 1..len {
+        assume_impl((i >= kept) | (order[i - 1] < order[i]), "i >= kept || order[i - 1] < order[i]",
+                    file!(), line!(), column!());
+    }
+    kept
+}
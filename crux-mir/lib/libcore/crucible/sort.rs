@@ -0,0 +1,69 @@
+//! A model of sorting, used in place of the sort algorithms in `libcore` and `liballoc`.
+
+use super::symbolic::{assume_impl, bool_is_concrete, symbolic_usize};
+
+/// Sort `v` by `is_less` without running a sort algorithm on symbolic comparisons.  While the
+/// comparisons are concrete, this runs an insertion sort.  Once one is symbolic, the elements are
+/// put in a symbolic order, which is then assumed to be sorted.
+///
+/// The order is built by swapping each position `i` with a symbolic position in `i .. len`, as in
+/// a Fisher-Yates shuffle, so every order is a permutation of `v` and every permutation can be
+/// chosen.  If `order` is given, it must have the same length as `v`; its elements are permuted
+/// along with those of `v`, and the sort is assumed to be stable with respect to it, so elements
+/// that compare equal stay in increasing order of their entries in `order`.
+///
+/// If `is_less` is not a strict weak order, the assumptions may be unsatisfiable, which makes
+/// every later assertion on the path vacuous.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn sort_by<T, F>(v: &mut [T], is_less: &mut F, mut order: Option<&mut [usize]>)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    if insertion_sort(v, is_less, &mut order) {
+        return;
+    }
+
+    let len = v.len();
+    for i in 0..len {
+        let j = symbolic_usize("sort_index");
+        assume_impl(i <= j && j < len, "i <= j && j < len", file!(), line!(), column!());
+        v.swap(i, j);
+        if let Some(order) = order.as_mut() {
+            order.swap(i, j);
+        }
+    }
+    for i in 1..len {
+        assume_impl(!is_less(&v[i], &v[i - 1]), "!(v[i] < v[i - 1])",
+                    file!(), line!(), column!());
+        if let Some(order) = order.as_ref() {
+            assume_impl(is_less(&v[i - 1], &v[i]) || order[i - 1] < order[i],
+                        "v[i - 1] < v[i] || order[i - 1] < order[i]", file!(), line!(), column!());
+        }
+    }
+}
+
+/// Insertion sort `v`, which is stable, and permute `order` along with it.  This stops and returns
+/// `false` at the first symbolic comparison, leaving `v` a permutation of the original.
+fn insertion_sort<T, F>(v: &mut [T], is_less: &mut F, order: &mut Option<&mut [usize]>) -> bool
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for i in 1..v.len() {
+        let mut j = i;
+        while j > 0 {
+            let less = is_less(&v[j], &v[j - 1]);
+            if !bool_is_concrete(less) {
+                return false;
+            }
+            if !less {
+                break;
+            }
+            v.swap(j, j - 1);
+            if let Some(order) = order.as_mut() {
+                order.swap(j, j - 1);
+            }
+            j -= 1;
+        }
+    }
+    true
+}
@@ -0,0 +1,133 @@
+//! A store buffer, which models the reorderings allowed by `Relaxed` and `Release` stores.
+//!
+//! A `Relaxed` or `Release` store to a modeled atomic doesn't write to memory right away.
+//! Instead the atomic holds it as *buffered*, along with the thread that made it.  A load by
+//! another thread chooses whether the buffered store has become visible yet, so both outcomes
+//! are explored; once it has, it's written to memory and every thread sees it.  Each atomic
+//! buffers at most one store, and a new store makes the previous one visible first, so all
+//! threads agree on the order of the stores to any one atomic, but stores to different atomics
+//! may become visible in any order.  This is the "partial store order" model.
+//!
+//! A thread always sees its own stores: its loads read its buffered store, if any.  Operations
+//! that publish a thread's earlier stores (a `Release` or `SeqCst` store, a read-modify-write, a
+//! `Release` fence, unlocking a mutex, spawning a thread, and exiting) call `flush_thread`, which
+//! makes all of them visible.  Rather than finding every atomic with such a store, this counts
+//! the thread's flushes, and a buffered store records the count when it's made: a store made
+//! before its thread's latest flush is visible to every thread.
+//!
+//! Since the buffered store lives inside the atomic, moving the atomic moves it along, and
+//! dropping the atomic discards it.  No store is ever written through a stale address.
+
+use super::concurrency;
+use super::symbolic::symbolic_usize;
+use crate::cell::UnsafeCell;
+use crate::intrinsics;
+
+/// The number of threads whose flushes are counted separately.  Threads whose IDs are equal
+/// modulo this share a count, so a flush by one also publishes the others' stores.  That only
+/// rules out some reorderings; it never adds any.
+const THREAD_SLOTS: usize = 64;
+
+// The counts and `Pending` are only accessed with atomic intrinsics, so that `--data-races`
+// doesn't report the accesses from different threads.  None of these accesses is a scheduling
+// point, so each update below is done before any other thread runs.
+struct Flushes(UnsafeCell<[u32; THREAD_SLOTS]>);
+
+unsafe impl Sync for Flushes {}
+
+static FLUSHES: Flushes = Flushes(UnsafeCell::new([0; THREAD_SLOTS]));
+
+fn flushes(thread: u32) -> u32 {
+    let counts = unsafe { intrinsics::atomic_load_relaxed(FLUSHES.0.get()) };
+    counts[thread as usize % THREAD_SLOTS]
+}
+
+#[derive(Clone, Copy)]
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub struct Buffered<T> {
+    /// The thread that made the store.
+    owner: u32,
+    /// `flushes(owner)` when the store was made.
+    flushes: u32,
+    val: T,
+}
+
+/// The store buffered in a modeled atomic, if any.  The field is public to the crate so that the
+/// atomics' `const fn` constructors can build it.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub struct Pending<T>(pub(crate) UnsafeCell<Option<Buffered<T>>>);
+
+impl<T: Copy> Pending<T> {
+    fn get(&self) -> Option<Buffered<T>> {
+        unsafe { intrinsics::atomic_load_relaxed(self.0.get()) }
+    }
+
+    fn set(&self, b: Option<Buffered<T>>) {
+        unsafe { intrinsics::atomic_store_relaxed(self.0.get(), b) }
+    }
+}
+
+/// Write the buffered store, if any, to `dst`, the value of the atomic that owns `pending`.
+fn commit<T: Copy>(dst: *mut T, pending: &Pending<T>) {
+    if let Some(b) = pending.get() {
+        unsafe { intrinsics::atomic_store_relaxed(dst, b.val) };
+        pending.set(None);
+    }
+}
+
+/// Buffer a store of `val` to `dst`, the value of the modeled atomic that owns `pending`.  A
+/// `Release` store first publishes the thread's earlier stores.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub unsafe fn store<T: Copy>(dst: *mut T, val: T, release: bool, pending: &Pending<T>) {
+    if release {
+        flush_thread();
+    }
+    commit(dst, pending);
+    let owner = concurrency::current_thread();
+    pending.set(Some(Buffered { owner, flushes: flushes(owner), val }));
+}
+
+/// Whether a load from the atomic that owns `pending` only reads memory.  It doesn't if another
+/// thread's store is buffered, since the load may make it visible.  This is passed to the load's
+/// `sched_yield`.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn load_is_ro<T: Copy>(pending: &Pending<T>) -> bool {
+    match pending.get() {
+        Some(b) => b.owner == concurrency::current_thread(),
+        None => true,
+    }
+}
+
+/// Prepare a load from `dst`, the value of the atomic that owns `pending`.  This returns the
+/// running thread's own buffered store, if there is one.  Otherwise, another thread's buffered
+/// store is written to memory if its thread has flushed since, or if this load chooses to see
+/// it, and the load reads memory.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn load<T: Copy>(dst: *mut T, pending: &Pending<T>) -> Option<T> {
+    let b = pending.get()?;
+    if b.owner == concurrency::current_thread() {
+        return Some(b.val);
+    }
+    if flushes(b.owner) != b.flushes || symbolic_usize("store_visible") != 0 {
+        commit(dst, pending);
+    }
+    None
+}
+
+/// Make the buffered store to the atomic at `dst`, by any thread, visible.  This is used before
+/// an access that must see the atomic's latest value.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn settle<T: Copy>(dst: *mut T, pending: &Pending<T>) {
+    commit(dst, pending);
+}
+
+/// Make all of the running thread's buffered stores visible.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn flush_thread() {
+    let slot = concurrency::current_thread() as usize % THREAD_SLOTS;
+    unsafe {
+        let mut counts = intrinsics::atomic_load_relaxed(FLUSHES.0.get());
+        counts[slot] = counts[slot].wrapping_add(1);
+        intrinsics::atomic_store_relaxed(FLUSHES.0.get(), counts);
+    }
+}
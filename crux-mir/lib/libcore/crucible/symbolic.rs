@@ -0,0 +1,20 @@
+//! Crucible symbolic values and assumptions, used by models of libcore functions.
+
+/// Create a new symbolic `usize`.  `desc` is used to refer to this value when printing
+/// counterexamples.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn symbolic_usize(desc: &'static str) -> usize {
+    unimplemented!("symbolic::symbolic_usize")
+}
+
+/// Whether `b` is concrete on the current path.  The result is always concrete, so a model can
+/// branch on it to keep a simpler loop when its inputs are concrete.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn bool_is_concrete(b: bool) -> bool {
+    unimplemented!("symbolic::bool_is_concrete")
+}
+
+/// Assume that `cond` holds on the current path.  This has the same effect as
+/// `crucible::crucible_assume!`.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn assume_impl(cond: bool, cond_str: &'static str, file: &'static str, line: u32, col: u32) {}
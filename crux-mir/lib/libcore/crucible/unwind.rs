@@ -0,0 +1,15 @@
+//! Support for `catch_unwind`.
+
+/// Call `f(data)`, and return whether it panicked.  crux-mir replaces this function: while `f`
+/// runs, a panic unwinds back to here, running destructors along the way, instead of failing the
+/// test.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn try_call<T>(f: fn(&mut T), data: &mut T) -> bool {
+    f(data);
+    false
+}
+
+/// Reset the unwinding state at the start of a new thread, which isn't inside any `catch_unwind`
+/// yet.  crux-mir replaces this function.
+#[unstable(feature = "crucible_intrinsics", issue = "none")]
+pub fn start_thread() {}
@@ -188,30 +188,31 @@ static DEC_DIGITS_LUT: &[u8; 200] = b"0001020304050607080910111213141516171819\
 
 macro_rules! impl_Display {
     ($($t:ident),* as $u:ident via $conv_fn:ident named $name:ident) => {
-        fn $name(mut n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            let mut buf = [0; 39];
-            let mut curr = buf.len() as usize;
-            let lut = DEC_DIGITS_LUT;
-
-            // decode 2 more chars, if > 2 chars
-            while n >= 100 {
-                let d1 = ((n % 100) as usize) << 1;
-                n /= 100;
-                curr -= 2;
-                buf[curr .. curr + 2].copy_from_slice(&lut[d1 .. d1 + 2]);
+        // Crux: instead of stopping after the last digit, this computes all `max_digits` digits
+        // of `n`, and how many of them are significant, without branching on `n`.  Formatting a
+        // symbolic integer then produces one string of symbolic length, rather than a path for
+        // each length.  The digits go at the start of `buf`, so code that appends the string,
+        // such as `String::push_str`, can reserve space for the whole buffer (see
+        // `crucible::ptr::len_bound`) instead of a symbolic amount.
+        fn $name(n: $u, is_nonnegative: bool, max_digits: usize, f: &mut fmt::Formatter<'_>)
+                 -> fmt::Result {
+            // `rev[i]` is the digit for `10^i`.
+            let mut rev = [b'0'; 39];
+            let mut len = 1;
+            let mut m = n;
+            for i in 0 .. max_digits {
+                rev[i] = (m % 10) as u8 + b'0';
+                m /= 10;
+                len += (m != 0) as usize;
             }
 
-            // decode last 1 or 2 chars
-            if n < 10 {
-                curr -= 1;
-                buf[curr] = (n as u8) + b'0';
-            } else {
-                let d1 = (n as usize) << 1;
-                curr -= 2;
-                buf[curr .. curr + 2].copy_from_slice(&lut[d1 .. d1 + 2]);
+            let mut buf = [0; 39];
+            for i in 0 .. max_digits {
+                // For `i < len`, the index is `len - 1 - i`.  The other bytes aren't used.
+                buf[i] = rev[(len + max_digits - 1 - i) % max_digits];
             }
 
-            let buf_slice = unsafe { str::from_utf8_unchecked(&buf[curr..]) };
+            let buf_slice = unsafe { str::from_utf8_unchecked(&buf[..len]) };
             f.pad_integral(is_nonnegative, "", buf_slice)
         }
 
@@ -227,12 +228,22 @@ macro_rules! impl_Display {
                         // convert the negative num to positive by summing 1 to it's 2 complement
                         (!self.$conv_fn()).wrapping_add(1)
                     };
-                    $name(n, is_nonnegative, f)
+                    $name(n, is_nonnegative, dec_digits(<$t>::MAX as u128), f)
                 }
             })*
     };
 }
 
+/// The number of decimal digits in `n`.
+fn dec_digits(mut n: u128) -> usize {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
+}
+
 macro_rules! impl_Exp {
     ($($t:ident),* as $u:ident via $conv_fn:ident named $name:ident) => {
         fn $name(
@@ -1132,14 +1132,19 @@ $EndFeature, "
                           without modifying the original"]
             #[inline]
             pub const fn saturating_mul(self, rhs: Self) -> Self {
-                match self.checked_mul(rhs) {
-                    Some(x) => x,
-                    None => if (self < 0) == (rhs < 0) {
-                        Self::max_value()
-                    } else {
-                        Self::min_value()
+                // Crux: the hook is overridden to compute the product and clamp it in a single
+                // expression, instead of branching on whether it overflows.
+                const fn crucible_saturating_mul_hook(x: $SelfT, y: $SelfT) -> $SelfT {
+                    match x.overflowing_mul(y) {
+                        (a, false) => a,
+                        (_, true) => if (x < 0) == (y < 0) {
+                            <$SelfT>::max_value()
+                        } else {
+                            <$SelfT>::min_value()
+                        }
                     }
                 }
+                crucible_saturating_mul_hook(self, rhs)
             }
         }
 
@@ -3246,10 +3251,15 @@ assert_eq!((", stringify!($SelfT), "::MAX).saturating_mul(10), ", stringify!($Se
                           without modifying the original"]
             #[inline]
             pub const fn saturating_mul(self, rhs: Self) -> Self {
-                match self.checked_mul(rhs) {
-                    Some(x) => x,
-                    None => Self::max_value(),
+                // Crux: the hook is overridden to compute the product and clamp it in a single
+                // expression, instead of branching on whether it overflows.
+                const fn crucible_saturating_mul_hook(x: $SelfT, y: $SelfT) -> $SelfT {
+                    match x.overflowing_mul(y) {
+                        (a, false) => a,
+                        (_, true) => <$SelfT>::max_value(),
+                    }
                 }
+                crucible_saturating_mul_hook(self, rhs)
             }
         }
 
@@ -5164,39 +5174,34 @@ fn from_str_radix<T: FromStrRadixHelper>(src: &str, radix: u32) -> Result<T, Par
         return Err(PIE { kind: Empty });
     }
 
+    // Crux: no digit returns early, so on symbolic input the paths for a digit
+    // being valid or invalid, and for a step overflowing or not, merge again
+    // after each digit instead of each reaching the end separately.  The error
+    // is still that of the first invalid digit or overflowing step.
     let mut result = T::from_u32(0);
-    if is_positive {
-        // The number is positive
-        for &c in digits {
-            let x = match (c as char).to_digit(radix) {
-                Some(x) => x,
-                None => return Err(PIE { kind: InvalidDigit }),
-            };
-            result = match result.checked_mul(radix) {
-                Some(result) => result,
-                None => return Err(PIE { kind: Overflow }),
-            };
-            result = match result.checked_add(x) {
-                Some(result) => result,
-                None => return Err(PIE { kind: Overflow }),
-            };
-        }
-    } else {
-        // The number is negative
-        for &c in digits {
-            let x = match (c as char).to_digit(radix) {
-                Some(x) => x,
-                None => return Err(PIE { kind: InvalidDigit }),
-            };
-            result = match result.checked_mul(radix) {
-                Some(result) => result,
-                None => return Err(PIE { kind: Underflow }),
-            };
-            result = match result.checked_sub(x) {
-                Some(result) => result,
-                None => return Err(PIE { kind: Underflow }),
-            };
-        }
+    let mut failed = false;
+    let mut invalid_digit = false;
+    for &c in digits {
+        let x = (c as char).to_digit(radix);
+        let step = match x {
+            Some(x) => result.checked_mul(radix).and_then(|r| {
+                if is_positive { r.checked_add(x) } else { r.checked_sub(x) }
+            }),
+            None => None,
+        };
+        invalid_digit |= !failed & x.is_none();
+        failed |= step.is_none();
+        result = step.unwrap_or(result);
+    }
+    if failed {
+        let kind = if invalid_digit {
+            InvalidDigit
+        } else if is_positive {
+            Overflow
+        } else {
+            Underflow
+        };
+        return Err(PIE { kind });
     }
     Ok(result)
 }
@@ -1044,7 +1044,16 @@ impl<T> [T] {
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline]
     pub fn split_at(&self, mid: usize) -> (&[T], &[T]) {
-        (&self[..mid], &self[mid..])
+        // Crux: split with a single bounds check, like `split_at_mut`, instead of indexing twice,
+        // so a symbolic `mid` branches only on `mid <= len`.
+        let len = self.len();
+        let ptr = self.as_ptr();
+
+        unsafe {
+            assert!(mid <= len);
+
+            (from_raw_parts(ptr, mid), from_raw_parts(ptr.add(mid), len - mid))
+        }
     }
 
     /// Divides one mutable slice into two at an index.
@@ -1508,12 +1517,36 @@ impl<T> [T] {
     /// s.insert(idx, num);
     /// assert_eq!(s, [0, 1, 1, 1, 1, 2, 3, 5, 8, 13, 21, 34, 42, 55]);
     /// ```
+    ///
+    /// Crux: when the slice is sorted, instead of running the search loop,
+    /// this picks the insertion point as a fresh symbolic index, constrained
+    /// by the elements on either side of it, so the search doesn't branch on
+    /// the elements.  If there are multiple matches, the first one is returned.
+    /// The constraints only describe the right index for a sorted slice, and
+    /// could be unsatisfiable otherwise, making every later assertion on the
+    /// path vacuous, so an unsorted slice is searched with the original loop
+    /// instead and gets the same unspecified result as in `std`.
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn binary_search(&self, x: &T) -> Result<usize, usize>
     where
         T: Ord,
     {
-        self.binary_search_by(|p| p.cmp(x))
+        use crate::crucible::symbolic::{assume_impl, symbolic_usize};
+        let len = self.len();
+        let mut sorted = true;
+        for k in 1..len {
+            sorted &= self[k - 1] <= self[k];
+        }
+        if !sorted {
+            return self.binary_search_by(|p| p.cmp(x));
+        }
+        let i = symbolic_usize("binary_search_index");
+        assume_impl(i <= len, "i <= len", file!(), line!(), column!());
+        assume_impl(i == 0 || self[i - 1].cmp(x) == Less,
+                    "i == 0 || self[i - 1] < x", file!(), line!(), column!());
+        assume_impl(i == len || self[i].cmp(x) != Less,
+                    "i == len || self[i] >= x", file!(), line!(), column!());
+        if i < len && self[i].cmp(x) == Equal { Ok(i) } else { Err(i) }
     }
 
     /// Binary searches this sorted slice with a comparator function.
@@ -3315,22 +3348,69 @@ macro_rules! iterator {
                 self.next_back()
             }
 
+            // Crux: a loop over a slice of symbolic length runs inside a branch for each possible
+            // length.  Instead, visit every element the slice's allocation has room for, and apply
+            // `f` only to those in the iterator, so each element costs one branch that merges
+            // right away.  `sum` and `product` use `fold`, as do adapters like `map` and `copied`.
+            #[inline]
+            fn fold<B, F>(mut self, init: B, mut f: F) -> B where
+                F: FnMut(B, Self::Item) -> B,
+            {
+                let mut acc = init;
+                if mem::size_of::<T>() == 0 {
+                    while let Some(x) = self.next() {
+                        acc = f(acc, x);
+                    }
+                    return acc;
+                }
+                let n = len!(self);
+                let bound = crate::crucible::ptr::len_bound(self.ptr.as_ptr(), n);
+                for i in 0..bound {
+                    if i < n {
+                        acc = f(acc, unsafe { & $( $mut_ )* *self.ptr.as_ptr().add(i) });
+                    }
+                }
+                acc
+            }
+
+            // Crux: stopping at the first match leaves the rest of the search inside a branch for
+            // each element.  Instead, visit every element the allocation has room for, as `fold`
+            // does, and record the index of the first one that matches, so each element costs
+            // one branch that merges right away and the result is a single symbolic index.  On
+            // concrete elements, the result is concrete.  As with the original loop, `predicate`
+            // is not called on the elements after the first match.
             #[inline]
             #[rustc_inherit_overflow_checks]
             fn position<P>(&mut self, mut predicate: P) -> Option<usize> where
                 Self: Sized,
                 P: FnMut(Self::Item) -> bool,
             {
-                // The addition might panic on overflow.
                 let n = len!(self);
-                self.try_fold(0, move |i, x| {
-                    if predicate(x) { Err(i) }
-                    else { Ok(i + 1) }
-                }).err()
-                    .map(|i| {
-                        unsafe { assume(i < n) };
-                        i
-                    })
+                if mem::size_of::<T>() == 0 {
+                    // The addition might panic on overflow.
+                    return self.try_fold(0, move |i, x| {
+                        if predicate(x) { Err(i) }
+                        else { Ok(i + 1) }
+                    }).err()
+                        .map(|i| {
+                            unsafe { assume(i < n) };
+                            i
+                        });
+                }
+                // `k == n` means there's no match.
+                let mut k = n;
+                let bound = crate::crucible::ptr::len_bound(self.ptr.as_ptr(), n);
+                for i in 0..bound {
+                    if i < n && k == n {
+                        if predicate(unsafe { & $( $mut_ )* *self.ptr.as_ptr().add(i) }) {
+                            k = i;
+                        }
+                    }
+                }
+                let found = k < n;
+                let consumed = if found { k + 1 } else { n };
+                unsafe { self.post_inc_start(consumed as isize) };
+                if found { Some(k) } else { None }
             }
 
             #[inline]
@@ -5987,25 +6067,14 @@ trait SliceContains: Sized {
     fn slice_contains(&self, x: &[Self]) -> bool;
 }
 
+// Crux: `contains` uses `position` on slice iterators, which doesn't fork on each comparison.
+// The `memchr` specializations for `u8` and `i8` are removed, since they read the slice a word at
+// a time.
 impl<T> SliceContains for T
 where
     T: PartialEq,
 {
-    default fn slice_contains(&self, x: &[Self]) -> bool {
-        x.iter().any(|y| *y == *self)
-    }
-}
-
-impl SliceContains for u8 {
-    fn slice_contains(&self, x: &[Self]) -> bool {
-        memchr::memchr(*self, x).is_some()
-    }
-}
-
-impl SliceContains for i8 {
     fn slice_contains(&self, x: &[Self]) -> bool {
-        let byte = *self as u8;
-        let bytes: &[u8] = unsafe { from_raw_parts(x.as_ptr() as *const u8, x.len()) };
-        memchr::memchr(byte, bytes).is_some()
+        x.iter().position(|y| *y == *self).is_some()
     }
 }
@@ -599,6 +599,8 @@ where
 ///
 /// `limit` is the number of allowed imbalanced partitions before switching to `heapsort`. If zero,
 /// this function will immediately switch to heapsort.
+// Crux: unused, since `quicksort` is modeled.
+#[allow(dead_code)]
 fn recurse<'a, T, F>(mut v: &'a mut [T], is_less: &mut F, mut pred: Option<&'a T>, mut limit: usize)
 where
     F: FnMut(&T, &T) -> bool,
@@ -685,19 +687,15 @@ where
 }
 
 /// Sorts `v` using pattern-defeating quicksort, which is `O(n log n)` worst-case.
+///
+/// Crux: instead of running quicksort, this uses `crucible::sort::sort_by`, which runs an
+/// insertion sort on concrete comparisons, and otherwise picks a symbolic permutation of `v` and
+/// assumes that it is sorted.
 pub fn quicksort<T, F>(v: &mut [T], mut is_less: F)
 where
     F: FnMut(&T, &T) -> bool,
 {
-    // Sorting has no meaningful behavior on zero-sized types.
-    if mem::size_of::<T>() == 0 {
-        return;
-    }
-
-    // Limit the number of imbalanced partitions to `floor(log2(len)) + 1`.
-    let limit = mem::size_of::<usize>() * 8 - v.len().leading_zeros() as usize;
-
-    recurse(v, &mut is_less, None, limit);
+    crate::crucible::sort::sort_by(v, &mut is_less, None);
 }
 
 fn partition_at_index_loop<'a, T, F>(
@@ -125,6 +125,7 @@ use crate::intrinsics;
 use crate::hint::spin_loop;
 
 use crate::crucible::concurrency;
+use crate::crucible::store_buffer::{self, Pending};
 
 /// Signals the processor that it is inside a busy-wait spin-loop ("spin lock").
 ///
@@ -162,6 +163,7 @@ pub fn spin_loop_hint() {
 pub struct AtomicBool {
     v: UnsafeCell<u8>,
     model: bool,
+    pending: Pending<u8>,
 }
 
 #[cfg(target_has_atomic_load_store = "8")]
@@ -328,14 +330,22 @@ impl AtomicBool {
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_const_stable(feature = "const_atomic_new", since = "1.32.0")]
     pub const fn new(v: bool) -> AtomicBool {
-        AtomicBool { v: UnsafeCell::new(v as u8), model: true }
+        AtomicBool {
+            v: UnsafeCell::new(v as u8),
+            model: true,
+            pending: Pending(UnsafeCell::new(None)),
+        }
     }
 
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_const_stable(feature = "const_atomic_new", since = "1.32.0")]
     pub const fn new_unmodeled(v: bool) -> AtomicBool {
-        AtomicBool { v: UnsafeCell::new(v as u8), model: false }
+        AtomicBool {
+            v: UnsafeCell::new(v as u8),
+            model: false,
+            pending: Pending(UnsafeCell::new(None)),
+        }
     }
 
     /// Returns a mutable reference to the underlying [`bool`].
@@ -358,6 +368,7 @@ impl AtomicBool {
     #[inline]
     #[stable(feature = "atomic_access", since = "1.15.0")]
     pub fn get_mut(&mut self) -> &mut bool {
+        store_buffer::settle(self.v.get(), &self.pending);
         // SAFETY: the mutable reference guarantees unique ownership.
         unsafe { &mut *(self.v.get() as *mut bool) }
     }
@@ -378,6 +389,7 @@ impl AtomicBool {
     #[inline]
     #[stable(feature = "atomic_access", since = "1.15.0")]
     pub fn into_inner(self) -> bool {
+        store_buffer::settle(self.v.get(), &self.pending);
         self.v.into_inner() != 0
     }
 
@@ -412,7 +424,10 @@ impl AtomicBool {
         // SAFETY: any data races are prevented by atomic intrinsics and the raw
         // pointer passed in is valid because we got it from a reference.
         if self.model {
-            concurrency::sched_yield(true, self.v.get());
+            concurrency::sched_yield(store_buffer::load_is_ro(&self.pending), self.v.get());
+            if let Some(val) = store_buffer::load(self.v.get(), &self.pending) {
+                return val != 0;
+            }
         }
         unsafe {
             atomic_load(self.v.get(), order) != 0
@@ -452,9 +467,9 @@ impl AtomicBool {
         // pointer passed in is valid because we got it from a reference.
         if self.model {
             concurrency::sched_yield(false, self.v.get());
-        }
-        unsafe {
-            atomic_store(self.v.get(), val as u8, order);
+            unsafe { modeled_store(self.v.get(), val as u8, order, &self.pending) }
+        } else {
+            unsafe { atomic_store(self.v.get(), val as u8, order) }
         }
     }
 
@@ -486,7 +501,7 @@ impl AtomicBool {
     pub fn swap(&self, val: bool, order: Ordering) -> bool {
         // SAFETY: data races are prevented by atomic intrinsics.
         if self.model {
-            concurrency::sched_yield(false, self.v.get());
+            modeled_rmw(self.v.get(), &self.pending);
         }
         unsafe { atomic_swap(self.v.get(), val as u8, order) != 0 }
     }
@@ -586,7 +601,7 @@ impl AtomicBool {
         // SAFETY: data races are prevented by atomic intrinsics.
         //
         if self.model {
-            concurrency::sched_yield(false, self.v.get());
+            modeled_rmw(self.v.get(), &self.pending);
         }
         match unsafe {
             atomic_compare_exchange(self.v.get(), current as u8, new as u8, success, failure)
@@ -647,7 +662,7 @@ impl AtomicBool {
     ) -> Result<bool, bool> {
         // SAFETY: data races are prevented by atomic intrinsics.
         if self.model {
-            concurrency::sched_yield(false, self.v.get());
+            modeled_rmw(self.v.get(), &self.pending);
         }
         match unsafe {
             atomic_compare_exchange_weak(self.v.get(), current as u8, new as u8, success, failure)
@@ -697,7 +712,7 @@ impl AtomicBool {
     pub fn fetch_and(&self, val: bool, order: Ordering) -> bool {
         // SAFETY: data races are prevented by atomic intrinsics.
         if self.model {
-            concurrency::sched_yield(false, self.v.get());
+            modeled_rmw(self.v.get(), &self.pending);
         }
         unsafe { atomic_and(self.v.get(), val as u8, order) != 0 }
     }
@@ -796,7 +811,7 @@ impl AtomicBool {
     pub fn fetch_or(&self, val: bool, order: Ordering) -> bool {
         // SAFETY: data races are prevented by atomic intrinsics.
         if self.model {
-            concurrency::sched_yield(false, self.v.get());
+            modeled_rmw(self.v.get(), &self.pending);
         }
         unsafe { atomic_or(self.v.get(), val as u8, order) != 0 }
     }
@@ -841,7 +856,7 @@ impl AtomicBool {
     pub fn fetch_xor(&self, val: bool, order: Ordering) -> bool {
         // SAFETY: data races are prevented by atomic intrinsics.
         if self.model {
-            concurrency::sched_yield(false, self.v.get());
+            modeled_rmw(self.v.get(), &self.pending);
         }
         unsafe { atomic_xor(self.v.get(), val as u8, order) != 0 }
     }
@@ -1271,7 +1286,8 @@ macro_rules! atomic_int {
         #[repr(C, align($align))]
         pub struct $atomic_type {
             v: UnsafeCell<$int_type>,
-            model: bool
+            model: bool,
+            pending: Pending<$int_type>,
         }
 
         /// An atomic integer initialized to `0`.
@@ -1330,6 +1346,7 @@ let atomic_forty_two = ", stringify!($atomic_type), "::new(42);
                     Self {
                         v: UnsafeCell::new(v),
                         model: true,
+                        pending: Pending(UnsafeCell::new(None)),
                     }
                 }
             }
@@ -1371,6 +1388,7 @@ assert_eq!(some_var.load(Ordering::SeqCst), 5);
                 #[inline]
                 #[$stable_access]
                 pub fn get_mut(&mut self) -> &mut $int_type {
+                    store_buffer::settle(self.v.get(), &self.pending);
                     // SAFETY: the mutable reference guarantees unique ownership.
                     unsafe { &mut *self.v.get() }
                 }
@@ -1393,6 +1411,7 @@ assert_eq!(some_var.into_inner(), 5);
                 #[inline]
                 #[$stable_access]
                 pub fn into_inner(self) -> $int_type {
+                    store_buffer::settle(self.v.get(), &self.pending);
                     self.v.into_inner()
                 }
             }
@@ -1428,7 +1447,11 @@ assert_eq!(some_var.load(Ordering::Relaxed), 5);
                 pub fn load(&self, order: Ordering) -> $int_type {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(true, self.v.get());
+                        concurrency::sched_yield(
+                            store_buffer::load_is_ro(&self.pending), self.v.get());
+                        if let Some(val) = store_buffer::load(self.v.get(), &self.pending) {
+                            return val;
+                        }
                     }
                     unsafe { atomic_load(self.v.get(), order) }
                 }
@@ -1467,8 +1490,10 @@ assert_eq!(some_var.load(Ordering::Relaxed), 10);
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
                         concurrency::sched_yield(false, self.v.get());
+                        unsafe { modeled_store(self.v.get(), val, order, &self.pending) }
+                    } else {
+                        unsafe { atomic_store(self.v.get(), val, order) }
                     }
-                    unsafe { atomic_store(self.v.get(), val, order); }
                 }
             }
 
@@ -1500,7 +1525,7 @@ assert_eq!(some_var.swap(10, Ordering::Relaxed), 5);
                 pub fn swap(&self, val: $int_type, order: Ordering) -> $int_type {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe { atomic_swap(self.v.get(), val, order) }
                 }
@@ -1606,7 +1631,7 @@ assert_eq!(some_var.load(Ordering::Relaxed), 10);
                                         failure: Ordering) -> Result<$int_type, $int_type> {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe { atomic_compare_exchange(self.v.get(), current, new, success, failure) }
                 }
@@ -1662,7 +1687,7 @@ loop {
                                              failure: Ordering) -> Result<$int_type, $int_type> {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe {
                         atomic_compare_exchange_weak(self.v.get(), current, new, success, failure)
@@ -1700,7 +1725,7 @@ assert_eq!(foo.load(Ordering::SeqCst), 10);
                 pub fn fetch_add(&self, val: $int_type, order: Ordering) -> $int_type {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe { atomic_add(self.v.get(), val, order) }
                 }
@@ -1736,7 +1761,7 @@ assert_eq!(foo.load(Ordering::SeqCst), 10);
                 pub fn fetch_sub(&self, val: $int_type, order: Ordering) -> $int_type {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe { atomic_sub(self.v.get(), val, order) }
                 }
@@ -1775,7 +1800,7 @@ assert_eq!(foo.load(Ordering::SeqCst), 0b100001);
                 pub fn fetch_and(&self, val: $int_type, order: Ordering) -> $int_type {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe { atomic_and(self.v.get(), val, order) }
                 }
@@ -1815,7 +1840,7 @@ assert_eq!(foo.load(Ordering::SeqCst), !(0x13 & 0x31));
                 pub fn fetch_nand(&self, val: $int_type, order: Ordering) -> $int_type {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe { atomic_nand(self.v.get(), val, order) }
                 }
@@ -1854,7 +1879,7 @@ assert_eq!(foo.load(Ordering::SeqCst), 0b111111);
                 pub fn fetch_or(&self, val: $int_type, order: Ordering) -> $int_type {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe { atomic_or(self.v.get(), val, order) }
                 }
@@ -1893,7 +1918,7 @@ assert_eq!(foo.load(Ordering::SeqCst), 0b011110);
                 pub fn fetch_xor(&self, val: $int_type, order: Ordering) -> $int_type {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe { atomic_xor(self.v.get(), val, order) }
                 }
@@ -2008,7 +2033,7 @@ assert!(max_foo == 42);
                 pub fn fetch_max(&self, val: $int_type, order: Ordering) -> $int_type {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe { $max_fn(self.v.get(), val, order) }
                 }
@@ -2064,7 +2089,7 @@ assert_eq!(min_foo, 12);
                 pub fn fetch_min(&self, val: $int_type, order: Ordering) -> $int_type {
                     // SAFETY: data races are prevented by atomic intrinsics.
                     if self.model {
-                        concurrency::sched_yield(false, self.v.get());
+                        modeled_rmw(self.v.get(), &self.pending);
                     }
                     unsafe { $min_fn(self.v.get(), val, order) }
                 }
@@ -2374,6 +2399,33 @@ unsafe fn atomic_store<T: Copy>(dst: *mut T, val: T, order: Ordering) {
     }
 }
 
+// Crux: a store to a modeled atomic.  `Relaxed` and `Release` stores go through the store
+// buffer, so that they may become visible to other threads later (see
+// `crucible::store_buffer`).  A `SeqCst` store is visible immediately, so it publishes the
+// thread's buffered stores, and any buffered store to the same atomic, first.
+#[inline]
+unsafe fn modeled_store<T: Copy>(dst: *mut T, val: T, order: Ordering, pending: &Pending<T>) {
+    match order {
+        Relaxed => store_buffer::store(dst, val, false, pending),
+        Release => store_buffer::store(dst, val, true, pending),
+        _ => {
+            store_buffer::flush_thread();
+            store_buffer::settle(dst, pending);
+            atomic_store(dst, val, order)
+        }
+    }
+}
+
+// Crux: the scheduling point before a read-modify-write of a modeled atomic.  These are not
+// buffered and act as full fences, whatever their ordering, so that the operation reads the
+// atomic's latest value.
+#[inline]
+fn modeled_rmw<T: Copy>(dst: *mut T, pending: &Pending<T>) {
+    concurrency::sched_yield(false, dst);
+    store_buffer::flush_thread();
+    store_buffer::settle(dst, pending);
+}
+
 #[inline]
 unsafe fn atomic_load<T: Copy>(dst: *const T, order: Ordering) -> T {
     match order {
@@ -2655,6 +2707,10 @@ unsafe fn atomic_umin<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[stable(feature = "rust1", since = "1.0.0")]
 #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
 pub fn fence(order: Ordering) {
+    // Crux: a fence that orders earlier stores waits for the thread's buffered stores.
+    if order != Acquire {
+        store_buffer::flush_thread();
+    }
     // On wasm32 it looks like fences aren't implemented in LLVM yet in that
     // they will cause LLVM to abort. The wasm instruction set doesn't have
     // fences right now. There's discussion online about the best way for tools
@@ -374,6 +374,9 @@ impl<F: Future> Future for AssertUnwindSafe<F> {
 /// aborting the process as well. This function *only* catches unwinding panics,
 /// not those that abort the process.
 ///
+/// Under crux-mir, `f` doesn't need to be `UnwindSafe`, so closures that
+/// capture mutable references can be tested without `AssertUnwindSafe`.
+///
 /// # Examples
 ///
 /// ```
@@ -390,7 +393,7 @@ impl<F: Future> Future for AssertUnwindSafe<F> {
 /// assert!(result.is_err());
 /// ```
 #[stable(feature = "catch_unwind", since = "1.9.0")]
-pub fn catch_unwind<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> Result<R> {
+pub fn catch_unwind<F: FnOnce() -> R, R>(f: F) -> Result<R> {
     unsafe { panicking::r#try(f) }
 }
 
@@ -421,7 +424,11 @@ pub fn catch_unwind<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> Result<R> {
 ///     panic::resume_unwind(err);
 /// }
 /// ```
+///
+/// Crux: the panic runtime isn't modeled, so this drops `payload` and starts a new panic, which
+/// unwinds the same way.  A `catch_unwind` that catches it gets the usual placeholder payload.
 #[stable(feature = "resume_unwind", since = "1.9.0")]
 pub fn resume_unwind(payload: Box<dyn Any + Send>) -> ! {
-    panicking::rust_panic_without_hook(payload)
+    drop(payload);
+    panic!("panic resumed by resume_unwind")
 }
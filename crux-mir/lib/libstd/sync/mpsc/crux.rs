@@ -0,0 +1,150 @@
+/// Channel implementation for crux-mir.
+///
+/// The upstream channel flavors are built on lock-free queues and thread parking, which the
+/// scheduler can't see.  Instead, all flavors share a single packet: a queue protected by a
+/// `Mutex`, with a `Condvar` to block receivers (and senders on full bounded channels) until the
+/// other side makes progress.  Since the queue is FIFO and each `send` enqueues under the lock,
+/// values from any one sender are received in the order they were sent.
+pub use self::Failure::*;
+
+use crate::collections::VecDeque;
+use crate::sync::{Condvar, Mutex, MutexGuard};
+
+use super::TrySendError;
+
+pub enum Failure {
+    Empty,
+    Disconnected,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    /// Number of live `Sender`s or `SyncSender`s.
+    senders: usize,
+    /// Whether the `Receiver` is still alive.
+    receiver: bool,
+    /// Number of values ever sent and received.  A send on a zero-capacity channel waits until
+    /// its value has been received.
+    sent: usize,
+    received: usize,
+}
+
+pub struct Packet<T> {
+    state: Mutex<State<T>>,
+    cond: Condvar,
+    /// Capacity of a bounded channel, or `None` for an unbounded one.
+    bound: Option<usize>,
+}
+
+impl<T> Packet<T> {
+    pub fn new(bound: Option<usize>) -> Packet<T> {
+        Packet {
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                senders: 1,
+                receiver: true,
+                sent: 0,
+                received: 0,
+            }),
+            cond: Condvar::new(),
+            bound,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+
+    fn wait<'a>(&self, guard: MutexGuard<'a, State<T>>) -> MutexGuard<'a, State<T>> {
+        self.cond.wait(guard).unwrap()
+    }
+
+    fn is_full(&self, state: &State<T>) -> bool {
+        match self.bound {
+            // A zero-capacity channel still holds the value being handed off.
+            Some(bound) => state.queue.len() >= bound.max(1),
+            None => false,
+        }
+    }
+
+    pub fn send(&self, t: T) -> Result<(), T> {
+        let mut state = self.lock();
+        while state.receiver && self.is_full(&state) {
+            state = self.wait(state);
+        }
+        if !state.receiver {
+            return Err(t);
+        }
+        state.queue.push_back(t);
+        state.sent += 1;
+        let ticket = state.sent;
+        self.cond.notify_all();
+        if self.bound == Some(0) {
+            while state.receiver && state.received < ticket {
+                state = self.wait(state);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.lock();
+        if !state.receiver {
+            return Err(TrySendError::Disconnected(t));
+        }
+        if self.is_full(&state) {
+            return Err(TrySendError::Full(t));
+        }
+        state.queue.push_back(t);
+        state.sent += 1;
+        self.cond.notify_all();
+        Ok(())
+    }
+
+    pub fn try_recv(&self) -> Result<T, Failure> {
+        let mut state = self.lock();
+        match state.queue.pop_front() {
+            Some(t) => {
+                state.received += 1;
+                self.cond.notify_all();
+                Ok(t)
+            }
+            None if state.senders == 0 => Err(Disconnected),
+            None => Err(Empty),
+        }
+    }
+
+    pub fn recv(&self) -> Result<T, Failure> {
+        let mut state = self.lock();
+        loop {
+            if let Some(t) = state.queue.pop_front() {
+                state.received += 1;
+                self.cond.notify_all();
+                return Ok(t);
+            }
+            if state.senders == 0 {
+                return Err(Disconnected);
+            }
+            state = self.wait(state);
+        }
+    }
+
+    pub fn clone_chan(&self) {
+        self.lock().senders += 1;
+    }
+
+    pub fn drop_chan(&self) {
+        let mut state = self.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            self.cond.notify_all();
+        }
+    }
+
+    pub fn drop_port(&self) {
+        let mut state = self.lock();
+        state.receiver = false;
+        state.queue.clear();
+        self.cond.notify_all();
+    }
+}
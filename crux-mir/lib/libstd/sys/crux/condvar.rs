@@ -1,7 +1,12 @@
+use crate::cell::Cell;
 use crate::sys::crux::mutex::Mutex;
 use crate::time::Duration;
+use core::crucible::concurrency;
 
 pub struct Condvar {
+    // `cond_wait` and `cond_signal` identify the condvar by its address, so it must not be zero
+    // sized.
+    _dummy: Cell<u8>,
 }
 
 unsafe impl Send for Condvar {}
@@ -9,7 +14,7 @@ unsafe impl Sync for Condvar {}
 
 impl Condvar {
     pub const fn new() -> Condvar {
-        Condvar {}
+        Condvar { _dummy: Cell::new(0) }
     }
 
     pub unsafe fn init(&mut self) {
@@ -18,17 +23,21 @@ impl Condvar {
 
     #[inline]
     pub unsafe fn notify_one(&self) {
-        // No-op
+        concurrency::cond_signal_one(self);
     }
 
     #[inline]
     pub unsafe fn notify_all(&self) {
-        // No-op
+        concurrency::cond_signal(self);
     }
 
     #[inline]
     pub unsafe fn wait(&self, mutex: &Mutex) {
-        panic!("deadlock: single-threaded program is waiting on a condvar");
+        // `mutex_unlock` never yields, so no other thread can signal between the unlock and the
+        // wait.
+        mutex.unlock();
+        concurrency::cond_wait(self, mutex);
+        mutex.lock();
     }
 
     #[inline]
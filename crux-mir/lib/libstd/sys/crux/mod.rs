@@ -1,4 +1,5 @@
 pub mod condvar;
 pub mod mutex;
 pub mod rwlock;
+pub mod thread_local;
 pub mod time;
@@ -1,7 +1,7 @@
 #![allow(dead_code)]
 use crate::cell::Cell;
 use crate::mem;
-use core::crucible::concurrency;
+use core::crucible::{concurrency, store_buffer};
 
 pub struct Mutex {
     locked: Cell<bool>,
@@ -22,18 +22,18 @@ impl Mutex {
     }
     #[inline]
     pub unsafe fn lock(&self) {
-        // TODO: Currently, we never run `drop` code, so locks are never released.  Once we support
-        // drops, we can enable this assertion to check for (invalid) reentrant locking.
-        // assert!(!self.locked.get());
         concurrency::mutex_lock(self);
+        // The scheduler only lets us past `mutex_lock` once no other thread holds the lock, so
+        // this only fails on (invalid) reentrant locking.
+        assert!(!self.locked.get());
         self.locked.set(true);
     }
     #[inline]
     pub unsafe fn unlock(&self) {
-        // TODO: Currently, we never run `drop` code, so locks are never released.  Once we support
-        // drops, we can enable this assertion to check for invalid usage.
+        assert!(self.locked.get());
+        // Everything done while holding the lock is visible to the next thread to take it.
+        store_buffer::flush_thread();
         concurrency::mutex_unlock(self);
-        // assert!(self.locked.get());
         self.locked.set(false);
     }
     #[inline]
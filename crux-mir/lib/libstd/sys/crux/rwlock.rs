@@ -1,6 +1,13 @@
 use crate::cell::Cell;
+use crate::sys::crux::condvar::Condvar;
+use crate::sys::crux::mutex::Mutex;
 
+/// A reader-writer lock built from a `Mutex` that protects its state and a `Condvar` on which
+/// threads wait until they can take it, so the scheduler sees each acquisition and release.  Any
+/// number of readers can hold the lock at once, but a writer excludes all other holders.
 pub struct RWLock {
+    state: Mutex,
+    cond: Condvar,
     num_readers: Cell<usize>,
     write_locked: Cell<bool>,
 }
@@ -11,56 +18,73 @@ unsafe impl Sync for RWLock {}
 impl RWLock {
     pub const fn new() -> RWLock {
         RWLock {
+            state: Mutex::new(),
+            cond: Condvar::new(),
             num_readers: Cell::new(0),
             write_locked: Cell::new(false),
         }
     }
     #[inline]
     pub unsafe fn read(&self) {
-        // TODO: Currently, we never run `drop` code, so locks are never released.  Once we support
-        // drops, we can enable this assertion to check for invalid usage.
-        //assert!(!self.write_locked.get());
+        self.state.lock();
+        while self.write_locked.get() {
+            self.cond.wait(&self.state);
+        }
         self.num_readers.set(self.num_readers.get() + 1);
+        self.state.unlock();
     }
 
     #[inline]
     pub unsafe fn try_read(&self) -> bool {
-        if self.write_locked.get() {
-            false
-        } else {
-            self.read();
-            true
+        self.state.lock();
+        let ok = !self.write_locked.get();
+        if ok {
+            self.num_readers.set(self.num_readers.get() + 1);
         }
+        self.state.unlock();
+        ok
     }
 
     #[inline]
     pub unsafe fn write(&self) {
-        // TODO: Currently, we never run `drop` code, so locks are never released.  Once we support
-        // drops, we can enable this assertion to check for invalid usage.
-        //assert!(!self.write_locked.get());
-        //assert!(self.num_readers.get() == 0);
+        self.state.lock();
+        while self.write_locked.get() || self.num_readers.get() > 0 {
+            self.cond.wait(&self.state);
+        }
         self.write_locked.set(true);
+        self.state.unlock();
     }
 
     #[inline]
     pub unsafe fn try_write(&self) -> bool {
-        if self.write_locked.get() || self.num_readers.get() > 0 {
-            false
-        } else {
-            self.write();
-            true
+        self.state.lock();
+        let ok = !self.write_locked.get() && self.num_readers.get() == 0;
+        if ok {
+            self.write_locked.set(true);
         }
+        self.state.unlock();
+        ok
     }
 
     #[inline]
     pub unsafe fn read_unlock(&self) {
+        self.state.lock();
+        assert!(self.num_readers.get() > 0);
         self.num_readers.set(self.num_readers.get() - 1);
+        if self.num_readers.get() == 0 {
+            // Only writers wait while the lock is read-locked.
+            self.cond.notify_all();
+        }
+        self.state.unlock();
     }
 
     #[inline]
     pub unsafe fn write_unlock(&self) {
+        self.state.lock();
         assert!(self.write_locked.get());
         self.write_locked.set(false);
+        self.cond.notify_all();
+        self.state.unlock();
     }
 
     #[inline]
@@ -0,0 +1,70 @@
+//! Keys for `thread_local!`, with a separate value for each thread.
+//!
+//! Crucible runs all threads in one address space, so a `#[thread_local]` static would be shared
+//! by every thread.  Instead, each key keeps a list of the threads' values.  A thread's value
+//! is created by the key's initializer the first time that thread accesses the key, and is never
+//! destroyed: destructors of thread-local values don't run when a thread exits.
+
+use crate::cell::UnsafeCell;
+use crate::fmt;
+use crate::ptr;
+use core::crucible::concurrency;
+use core::intrinsics;
+
+/// One thread's value.  Each key has a list of these, newest first, so any number of threads can
+/// access it.  It's a list rather than a `Vec` so that each entry can be read and published with
+/// an atomic intrinsic, as in `core::crucible::store_buffer`: that way `--data-races` doesn't
+/// report the accesses from different threads, and none of them is a scheduling point.  Entries
+/// are never freed.
+struct Entry<T> {
+    owner: u32,
+    value: *const T,
+    next: *const Entry<T>,
+}
+
+impl<T> Clone for Entry<T> {
+    fn clone(&self) -> Entry<T> {
+        *self
+    }
+}
+
+impl<T> Copy for Entry<T> {}
+
+pub struct Key<T> {
+    head: UnsafeCell<*const Entry<T>>,
+}
+
+unsafe impl<T> Sync for Key<T> {}
+
+impl<T> fmt::Debug for Key<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Key { .. }")
+    }
+}
+
+impl<T> Key<T> {
+    pub const fn new() -> Key<T> {
+        Key { head: UnsafeCell::new(ptr::null()) }
+    }
+
+    pub unsafe fn get<F: FnOnce() -> T>(&self, init: F) -> Option<&'static T> {
+        let me = concurrency::current_thread();
+        let mut next = intrinsics::atomic_load_relaxed(self.head.get());
+        while !next.is_null() {
+            let entry = intrinsics::atomic_load_relaxed(next);
+            if entry.owner == me {
+                return Some(&*entry.value);
+            }
+            next = entry.next;
+        }
+
+        // The initializer can access other keys, or even this one, so it runs before the list is
+        // updated.  If it initialized this key recursively, the new entry comes first and so
+        // replaces that value, as in `std`.
+        let value = Box::into_raw(Box::new(init())) as *const T;
+        let head = intrinsics::atomic_load_relaxed(self.head.get());
+        let entry = Box::into_raw(Box::new(Entry { owner: me, value, next: head }));
+        intrinsics::atomic_store_relaxed(self.head.get(), entry as *const Entry<T>);
+        Some(&*value)
+    }
+}
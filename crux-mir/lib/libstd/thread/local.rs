@@ -153,24 +153,10 @@ macro_rules! __thread_local_inner {
             fn __init() -> $t { $init }
 
             unsafe fn __getit() -> $crate::option::Option<&'static $t> {
-                #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
-                static __KEY: $crate::thread::__StaticLocalKeyInner<$t> =
-                    $crate::thread::__StaticLocalKeyInner::new();
-
-                #[thread_local]
-                #[cfg(all(
-                    target_thread_local,
-                    not(all(target_arch = "wasm32", not(target_feature = "atomics"))),
-                ))]
-                static __KEY: $crate::thread::__FastLocalKeyInner<$t> =
-                    $crate::thread::__FastLocalKeyInner::new();
-
-                #[cfg(all(
-                    not(target_thread_local),
-                    not(all(target_arch = "wasm32", not(target_feature = "atomics"))),
-                ))]
-                static __KEY: $crate::thread::__OsLocalKeyInner<$t> =
-                    $crate::thread::__OsLocalKeyInner::new();
+                // Crux: `#[thread_local]` statics would be shared by all threads, so every
+                // platform uses a key that keeps a separate value for each thread.
+                static __KEY: $crate::thread::__CruxLocalKeyInner<$t> =
+                    $crate::thread::__CruxLocalKeyInner::new();
 
                 __KEY.get(__init)
             }
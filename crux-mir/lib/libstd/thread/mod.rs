@@ -188,6 +188,11 @@ mod local;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::local::{AccessError, LocalKey};
 
+mod scoped;
+
+#[stable(feature = "scoped_threads", since = "1.63.0")]
+pub use self::scoped::{scope, Scope, ScopedJoinHandle};
+
 // The types used by the thread_local! macro to access TLS keys. Note that there
 // are two types, the "OS" type and the "fast" type. The OS thread local key
 // type is accessed via platform-specific API calls and is slow, while the fast
@@ -208,6 +213,10 @@ pub use self::local::os::Key as __OsLocalKeyInner;
 #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
 #[doc(hidden)]
 pub use self::local::statik::Key as __StaticLocalKeyInner;
+// Crux: the key type that `thread_local!` actually uses, which keeps a value for each thread.
+#[unstable(feature = "libstd_thread_internals", issue = "none")]
+#[doc(hidden)]
+pub use crate::sys::crux::thread_local::Key as __CruxLocalKeyInner;
 
 use core::crucible::concurrency;
 
@@ -623,25 +632,30 @@ where
     F: Send + 'static,
     T: Send + 'static,
 {
-    let id = concurrency::spawn(f);
-    let t =
-        Thread {
-            inner: Arc::new(Inner {
-                name: None,
-                id: ThreadId(NonZeroU64::new(id.into()).unwrap()),
-                state: AtomicUsize::new(EMPTY),
-                lock: Mutex::new(()),
-                cvar: Condvar::new(),
-            }),
-        };
+    // The thread returns the `Result` that `join` returns, with an `Err` if
+    // `f` panicked.
+    let id = concurrency::spawn(move || panic::catch_unwind(panic::AssertUnwindSafe(f)));
     JoinHandle(
         JoinInner {
-            thread: t,
+            thread: thread_from_id(id),
             phantom: core::marker::PhantomData,
         }
     )
 }
 
+/// Build the `Thread` handle for the thread with the given crucible thread ID.
+fn thread_from_id(id: u32) -> Thread {
+    Thread {
+        inner: Arc::new(Inner {
+            name: None,
+            id: ThreadId(NonZeroU64::new(id.into()).unwrap()),
+            state: AtomicUsize::new(EMPTY),
+            lock: Mutex::new(()),
+            cvar: Condvar::new(),
+        }),
+    }
+}
+
 /// Gets a handle to the thread that invokes it.
 ///
 /// # Examples
@@ -1379,8 +1393,7 @@ struct JoinInner<T> {
 impl<T> JoinInner<T> {
     fn join(&mut self) -> Result<T> {
         let ThreadId(i) = self.thread.id();
-        let result      = concurrency::join(i.get());
-        Ok(result)
+        concurrency::join::<Result<T>>(i.get())
     }
 }
 
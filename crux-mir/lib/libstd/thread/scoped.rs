@@ -0,0 +1,155 @@
+//! Scoped threads.
+//!
+//! Crux: each scoped thread runs a wrapper closure that stores its result in a
+//! `Packet` and returns `()`, so that `scope` can join every thread without
+//! knowing its result type.
+
+use super::{thread_from_id, Packet, Result, Thread};
+use crate::cell::{RefCell, UnsafeCell};
+use crate::fmt;
+use crate::marker::PhantomData;
+use crate::panic;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::sync::Arc;
+use core::crucible::concurrency;
+
+/// A scope to spawn scoped threads in.
+///
+/// See [`scope`] for details.
+#[stable(feature = "scoped_threads", since = "1.63.0")]
+pub struct Scope<'scope, 'env: 'scope> {
+    /// IDs of all threads spawned in this scope.
+    threads: RefCell<Vec<u64>>,
+    /// The number of threads that panicked and haven't been joined explicitly.
+    unhandled_panics: AtomicUsize,
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+// Threads only switch at scheduling points, and none occur while `threads` is
+// borrowed, so sharing the `RefCell` between threads is safe.
+#[stable(feature = "scoped_threads", since = "1.63.0")]
+unsafe impl<'scope, 'env> Sync for Scope<'scope, 'env> {}
+
+/// An owned permission to join on a scoped thread (block on its termination).
+///
+/// See [`Scope::spawn`] for details.
+#[stable(feature = "scoped_threads", since = "1.63.0")]
+pub struct ScopedJoinHandle<'scope, T> {
+    thread: Thread,
+    packet: Packet<T>,
+    unhandled_panics: &'scope AtomicUsize,
+}
+
+/// Create a scope for spawning scoped threads.
+///
+/// The function passed to `scope` will be provided a [`Scope`] object,
+/// through which scoped threads can be [spawned][`Scope::spawn`].
+///
+/// Unlike non-scoped threads, scoped threads can borrow non-`'static` data,
+/// as the scope guarantees all threads will be joined at the end of the scope.
+///
+/// All threads spawned within the scope that haven't been manually joined
+/// will be automatically joined before this function returns.
+///
+/// # Panics
+///
+/// If any of the automatically joined threads panicked, this function will panic.
+///
+/// If you want to handle panics from spawned threads,
+/// [`join`][ScopedJoinHandle::join] them before the end of the scope.
+///
+/// If `f` panics, the threads are still joined before the panic continues.
+#[stable(feature = "scoped_threads", since = "1.63.0")]
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+{
+    let scope = Scope {
+        threads: RefCell::new(Vec::new()),
+        unhandled_panics: AtomicUsize::new(0),
+        scope: PhantomData,
+        env: PhantomData,
+    };
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| f(&scope)));
+    // Joining a thread that was already joined returns immediately.
+    let threads = scope.threads.borrow().clone();
+    for id in threads {
+        concurrency::join::<()>(id);
+    }
+    match result {
+        Err(e) => panic::resume_unwind(e),
+        Ok(_) if scope.unhandled_panics.load(Ordering::SeqCst) != 0 => {
+            panic!("a scoped thread panicked")
+        }
+        Ok(result) => result,
+    }
+}
+
+impl<'scope, 'env> Scope<'scope, 'env> {
+    /// Spawns a new thread within a scope, returning a [`ScopedJoinHandle`] for it.
+    ///
+    /// Unlike non-scoped threads, threads spawned with this function may
+    /// borrow non-`'static` data from the outside the scope. See [`scope`] for
+    /// details.
+    ///
+    /// If the join handle is dropped, the spawned thread will implicitly joined
+    /// at the end of the scope.
+    #[stable(feature = "scoped_threads", since = "1.63.0")]
+    pub fn spawn<F, T>(&'scope self, f: F) -> ScopedJoinHandle<'scope, T>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        let packet: Packet<T> = Packet(Arc::new(UnsafeCell::new(None)));
+        let their_packet = Packet(packet.0.clone());
+        let unhandled_panics = &self.unhandled_panics;
+        let main = move || {
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+            if result.is_err() {
+                unhandled_panics.fetch_add(1, Ordering::SeqCst);
+            }
+            unsafe { *their_packet.0.get() = Some(result) };
+        };
+        // Safety: `scope` joins this thread before `'scope` ends.
+        let id = unsafe { concurrency::spawn_scoped(main) };
+        self.threads.borrow_mut().push(id.into());
+        ScopedJoinHandle { thread: thread_from_id(id), packet, unhandled_panics }
+    }
+}
+
+impl<'scope, T> ScopedJoinHandle<'scope, T> {
+    /// Extracts a handle to the underlying thread.
+    #[stable(feature = "scoped_threads", since = "1.63.0")]
+    pub fn thread(&self) -> &Thread {
+        &self.thread
+    }
+
+    /// Waits for the associated thread to finish, and returns its result.
+    ///
+    /// If the thread panicked, this returns an `Err` with the panic payload.
+    #[stable(feature = "scoped_threads", since = "1.63.0")]
+    pub fn join(self) -> Result<T> {
+        concurrency::join::<()>(self.thread.id().0.get());
+        let result = unsafe { (*self.packet.0.get()).take().unwrap() };
+        if result.is_err() {
+            // The panic is handled here, so `scope` won't panic for it.
+            self.unhandled_panics.fetch_sub(1, Ordering::SeqCst);
+        }
+        result
+    }
+}
+
+#[stable(feature = "scoped_threads", since = "1.63.0")]
+impl fmt::Debug for Scope<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Scope { .. }")
+    }
+}
+
+#[stable(feature = "scoped_threads", since = "1.63.0")]
+impl<'scope, T> fmt::Debug for ScopedJoinHandle<'scope, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("ScopedJoinHandle { .. }")
+    }
+}
@@ -46,9 +46,13 @@ mirExplorePrimitives ::
   (C.IsSymInterface sym, W4.IsExprBuilder sym) => ExplorePrimitives p sym MIR
 mirExplorePrimitives =
   [ Match mirLock
+  , Match mirCond
   , Match mirAtomic
+  , Match mirDataAccess
   , Match mirJoin
   , Match mirSpawn
+  , Match mirSelf
+  , Match mirAtomicBlock
   , Match mirExit
   ]
 
@@ -71,6 +75,24 @@ mirAtomic _ nm ctx cf _
        pure $! ThreadYield SimpleYield refs isRO
   | otherwise = Nothing
 
+-- | The name of the function that the translation calls before each access
+-- through a raw pointer when checking for data races (see
+-- `Mir.Trans.noteDataAccess`).
+dataAccessName :: Text.Text
+dataAccessName = "crucible_data_access"
+
+mirDataAccess :: C.IsSymInterface sym => ExplorePrimitiveMatcher p sym MIR
+mirDataAccess _ nm ctx cf _
+  | W4.functionName nm == dataAccessName
+  , Ctx.Empty Ctx.:> C.BoolRepr Ctx.:> C.AnyRepr <- ctx
+  = do isWrite <- W4.asConstantPred =<< retrieveTypedArg ctx cf C.BoolRepr 0
+       C.AnyValue tpr ref <- retrieveTypedArg ctx cf C.AnyRepr 1
+       case tpr of
+         MirReferenceRepr _ ->
+           pure $! ThreadYield NonAtomic (mirRefName ref) (not isWrite)
+         _ -> error $ "crucible_data_access: unexpected argument type " ++ show tpr
+  | otherwise = Nothing
+
 mirLock :: C.IsSymInterface sym => ExplorePrimitiveMatcher p sym MIR
 mirLock _ nm ctx cf _
   | matchGeneric "core::crucible::concurrency::mutex_lock" nm
@@ -91,6 +113,32 @@ mirLock _ nm ctx cf _
          _ -> error "TODO: Muxed mutex unlock"
   | otherwise = Nothing
 
+mirCond :: C.IsSymInterface sym => ExplorePrimitiveMatcher p sym MIR
+mirCond _ nm ctx cf _
+  | matchGeneric "core::crucible::concurrency::cond_wait" nm
+  , Ctx.Empty Ctx.:> MirReferenceRepr tc Ctx.:> MirReferenceRepr tm <- ctx
+  = do cv <- retrieveTypedArg ctx cf (MirReferenceRepr tc) 0
+       mv <- retrieveTypedArg ctx cf (MirReferenceRepr tm) 1
+       case (mirRefName cv, mirRefName mv) of
+         ([c], [m]) ->
+           pure $! ThreadCondWait c m
+         _ -> error "TODO: Muxed condvar wait"
+  | matchGeneric "core::crucible::concurrency::cond_signal" nm
+  , Ctx.Empty Ctx.:> MirReferenceRepr t <- ctx
+  = do arg <- retrieveTypedArg ctx cf (MirReferenceRepr t) 0
+       case mirRefName arg of
+         [c] ->
+           pure $! ThreadCondSignal True c
+         _ -> error "TODO: Muxed condvar signal"
+  | matchGeneric "core::crucible::concurrency::cond_signal_one" nm
+  , Ctx.Empty Ctx.:> MirReferenceRepr t <- ctx
+  = do arg <- retrieveTypedArg ctx cf (MirReferenceRepr t) 0
+       case mirRefName arg of
+         [c] ->
+           pure $! ThreadCondSignal False c
+         _ -> error "TODO: Muxed condvar signal"
+  | otherwise = Nothing
+
 mutexName :: BV.BV 32 -> Text.Text
 mutexName bv = Text.pack ("resource-"++ show bv)
 
@@ -116,6 +164,21 @@ mirSpawn _ nm ctx cf@C.CallFrame { C._frameCFG = cfg } _
                 ++ show (ctx, C.handleArgTypes hdl, C.handleReturnType hdl)
   | otherwise = Nothing
 
+mirSelf :: C.IsSymInterface sym => ExplorePrimitiveMatcher p sym MIR
+mirSelf _ nm _ _ _
+  | textId (W4.functionName nm) == "core::crucible::concurrency::current_thread" =
+    let mkRet sym v = W4.bvLit sym W4.knownRepr (BV.mkBV BV.knownNat (fromIntegral v))
+    in pure $! ThreadSelf (C.BVRepr (W4.knownNat @32)) mkRet
+  | otherwise = Nothing
+
+mirAtomicBlock :: C.IsSymInterface sym => ExplorePrimitiveMatcher p sym MIR
+mirAtomicBlock _ nm _ _ _
+  | textId (W4.functionName nm) == "core::crucible::concurrency::atomic_begin" =
+    pure $! ThreadAtomic True
+  | textId (W4.functionName nm) == "core::crucible::concurrency::atomic_end" =
+    pure $! ThreadAtomic False
+  | otherwise = Nothing
+
 mirExit :: C.IsSymInterface sym => ExplorePrimitiveMatcher p sym MIR
 mirExit _ nm ctx cf _
   | matchGeneric "core::crucible::concurrency::spawn::thread_exit" nm =
@@ -77,10 +77,32 @@ needsRebuild output inputs = do
 mirJsonOutFile :: FilePath -> FilePath
 mirJsonOutFile rustFile = rustFile -<.> "mir"
 
+-- | The flags passed to `mir-json` that depend on crux-mir's own options.
+-- They're recorded in `mirJsonFlagsFile`, so that changing them rebuilds the
+-- `.mir` file even if the source file hasn't changed.
+mirJsonFlags :: (?overflowChecks :: Bool) => [String]
+mirJsonFlags =
+    -- Check for arithmetic overflow as in a debug build, regardless of
+    -- rustc's defaults.
+    ["-C", "overflow-checks=" ++ (if ?overflowChecks then "on" else "off")]
+
+mirJsonFlagsFile :: FilePath -> FilePath
+mirJsonFlagsFile rustFile = rustFile -<.> "mir-flags"
+
+-- | Check whether `flags` differ from those recorded in `flagsFile`, or
+-- nothing was recorded.
+flagsChanged :: FilePath -> [String] -> IO Bool
+flagsChanged flagsFile flags = doesFileExist flagsFile >>= \case
+    False -> return True
+    True -> do
+        old <- readFile flagsFile
+        -- Read the whole file, so it's closed before it's rewritten.
+        length old `seq` return (old /= show flags)
+
 getRlibsDir :: (?defaultRlibsDir :: FilePath) => IO FilePath
 getRlibsDir = maybe ?defaultRlibsDir id <$> lookupEnv "CRUX_RUST_LIBRARY_PATH"
 
-compileMirJson :: (?defaultRlibsDir :: FilePath) => Bool -> Bool -> FilePath -> IO ()
+compileMirJson :: (?defaultRlibsDir :: FilePath, ?overflowChecks :: Bool) => Bool -> Bool -> FilePath -> IO ()
 compileMirJson keepRlib quiet rustFile = do
     let outFile = rustFile -<.> "bin"
 
@@ -89,7 +111,7 @@ compileMirJson keepRlib quiet rustFile = do
     let cp = Proc.proc "mir-json"
             [rustFile, "-L", rlibsDir, "--crate-type=rlib", "--edition=2018"
             , "--cfg", "crux", "--cfg", "crux_top_level"
-            , "-o", outFile]
+            ] ++ mirJsonFlags ++ ["-o", outFile]
     let cp' = if not quiet then cp else
             (cp { Proc.std_out = Proc.NoStream, Proc.std_err = Proc.NoStream })
     ec <- Proc.withCreateProcess cp' $ \_ _ _ ph -> Proc.waitForProcess ph
@@ -103,10 +125,13 @@ compileMirJson keepRlib quiet rustFile = do
             True  -> removeFile outFile
             False -> return ()
 
-maybeCompileMirJson :: (?defaultRlibsDir :: FilePath) => Bool -> Bool -> FilePath -> IO ()
+maybeCompileMirJson :: (?defaultRlibsDir :: FilePath, ?overflowChecks :: Bool) => Bool -> Bool -> FilePath -> IO ()
 maybeCompileMirJson keepRlib quiet rustFile = do
     build <- needsRebuild (mirJsonOutFile rustFile) [rustFile]
-    when build $ compileMirJson keepRlib quiet rustFile
+    flagsStale <- flagsChanged (mirJsonFlagsFile rustFile) mirJsonFlags
+    when (build || flagsStale) $ do
+        compileMirJson keepRlib quiet rustFile
+        writeFile (mirJsonFlagsFile rustFile) (show mirJsonFlags)
 
 
 linkJson :: [FilePath] -> IO B.ByteString
@@ -159,7 +184,7 @@ libJsonFiles =
 -- NOTE: If the rust file has not been modified since the
 -- last .mir file was created, this function does nothing
 -- This function uses 'failIO' if any error occurs
-generateMIR :: (HasCallStack, ?debug::Int, ?defaultRlibsDir :: FilePath) =>
+generateMIR :: (HasCallStack, ?debug::Int, ?defaultRlibsDir :: FilePath, ?overflowChecks :: Bool) =>
                FilePath          -- ^ location of input file
             -> Bool              -- ^ `True` to keep the generated .rlib
             -> IO Collection
@@ -212,7 +237,8 @@ uninternMir col = uninternTys unintern (col { _namedTys = mempty })
 
 
 -- | Translate a MIR collection to Crucible
-translateMIR :: (HasCallStack, ?debug::Int, ?assertFalseOnError::Bool, ?printCrucible::Bool) 
+translateMIR :: (HasCallStack, ?debug::Int, ?assertFalseOnError::Bool, ?printCrucible::Bool,
+                 ?dataRaces::Bool)
    => CollectionState -> Collection -> C.HandleAllocator -> IO RustModule
 translateMIR lib col halloc =
   let ?customOps = Mir.customOps in
@@ -181,9 +181,31 @@ data CollectionState
       _staticMap      :: !(Map DefId StaticVar),
       -- | For Enums, gives the discriminant value for each variant.
       _discrMap       :: !(Map AdtName [Integer]),
-      _collection     :: !Collection
+      _collection     :: !Collection,
+      -- | When checking for data races, the function to call before each
+      -- access through a raw pointer.  See `Mir.Concurrency.mirDataAccess`.
+      _dataAccessHandle :: !(Maybe (FH.FnHandle DataAccessArgs C.UnitType)),
+      -- | The globals that track unwinding for `catch_unwind`.
+      _unwindVars     :: !(Maybe UnwindVars),
+      -- | The number of heap allocations made so far on the current path, for
+      -- `crucible::stats`.  See `noteAllocation`.
+      _allocCount     :: !(Maybe (G.GlobalVar UsizeType))
       }
 
+-- | The state of unwinding from panics.  Inside a call to `catch_unwind`
+-- (while the depth is nonzero), a panic sets the unwinding flag and returns
+-- instead of failing, and each caller returns in turn until the flag is
+-- cleared by the `catch_unwind`.  See `Mir.TransCustom.unwind_try_call`.
+data UnwindVars = UnwindVars
+  { uvCatchDepth :: !(G.GlobalVar UsizeType)
+  , uvUnwinding  :: !(G.GlobalVar C.BoolType)
+  }
+
+-- | Arguments to the data access function: whether the access is a write, and
+-- the accessed reference, packed as an `Any` so one function suffices for all
+-- pointee types.
+type DataAccessArgs = EmptyCtx ::> C.BoolType ::> C.AnyType
+
 
 ---------------------------------------------------------------------------
 -- ** Custom operations
@@ -221,6 +243,10 @@ data CustomOp      =
          [MirExp s]
       -> MirGenerator h s ret Text)
     -- ^ custom operations that don't return
+  | CustomOpPanic (forall h s ret.
+         [MirExp s]
+      -> MirGenerator h s ret Text)
+    -- ^ custom operations that panic, which unwind inside `catch_unwind`
 type CustomRHS = Substs -> Maybe CustomOp
 
 
@@ -369,10 +395,11 @@ instance Monoid RustModule where
   mempty  = RustModule mempty mempty mempty
 
 instance Semigroup CollectionState  where
-  (CollectionState hm1 vm1 sm1 dm1 col1) <> (CollectionState hm2 vm2 sm2 dm2 col2) =
-      (CollectionState (hm1 <> hm2) (vm1 <> vm2) (sm1 <> sm2) (dm1 <> dm2) (col1 <> col2))
+  (CollectionState hm1 vm1 sm1 dm1 col1 dh1 uv1 ac1) <> (CollectionState hm2 vm2 sm2 dm2 col2 dh2 uv2 ac2) =
+      (CollectionState (hm1 <> hm2) (vm1 <> vm2) (sm1 <> sm2) (dm1 <> dm2) (col1 <> col2)
+        (maybe dh2 Just dh1) (maybe uv2 Just uv1) (maybe ac2 Just ac1))
 instance Monoid CollectionState where
-  mempty  = CollectionState mempty mempty mempty mempty mempty
+  mempty  = CollectionState mempty mempty mempty mempty mempty Nothing Nothing Nothing
 
 
 instance Show (MirExp s) where
@@ -530,6 +557,15 @@ newMirRef ::
   MirGenerator h s ret (R.Expr MIR s (MirReferenceType tp))
 newMirRef tp = G.extensionStmt (MirNewRef tp)
 
+-- | Count a heap allocation, for `crucible::stats`.  The count is a global, so
+-- it's merged like other state and gives the allocations on the current path.
+noteAllocation :: MirGenerator h s ret ()
+noteAllocation = do
+    mac <- use $ cs . allocCount
+    forM_ mac $ \ac -> do
+        n <- G.readGlobal ac
+        G.writeGlobal ac $ R.App $ usizeAdd n $ R.App $ usizeLit 1
+
 integerToMirRef ::
   C.TypeRepr tp ->
   R.Expr MIR s UsizeType ->
@@ -643,6 +679,26 @@ mirRef_peelIndex tpr ref = do
     pair <- G.extensionStmt $ MirRef_PeelIndex tpr ref
     return (S.getStruct i1of2 pair, S.getStruct i2of2 pair)
 
+-- | Get the number of elements that can be accessed through `ref` and the
+-- references after it in the same allocation, or Nothing if the allocation
+-- has no fixed length.
+mirRef_available ::
+  R.Expr MIR s (MirReferenceType tp) ->
+  MirGenerator h s ret (R.Expr MIR s (C.MaybeType UsizeType))
+mirRef_available ref = do
+    m <- G.extensionStmt $ MirRef_IndexAndLen ref
+    G.caseMaybe m (C.MaybeRepr UsizeRepr) $ G.MatchMaybe
+        (\pair -> return $ R.App $ E.JustValue UsizeRepr $
+            R.App $ usizeSub (S.getStruct i2of2 pair) (S.getStruct i1of2 pair))
+        (return $ R.App $ E.NothingValue UsizeRepr)
+
+-- | Check whether a `usize` is concrete, to choose how to handle it.  The
+-- result is always concrete, so branching on it doesn't fork the execution.
+usizeIsConcrete ::
+  R.Expr MIR s UsizeType ->
+  MirGenerator h s ret (R.Expr MIR s C.BoolType)
+usizeIsConcrete x = G.extensionStmt $ MirUsizeIsConcrete x
+
 -----------------------------------------------------------------------
 
 
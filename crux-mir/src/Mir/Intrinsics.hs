@@ -102,6 +102,7 @@ import           Lang.Crucible.Simulator.SimError
 
 import           What4.Concrete (ConcreteVal(..), concreteType)
 import           What4.Interface
+import           What4.InterpretedFloatingPoint (iFloatRound)
 import           What4.Partial
     (PartExpr, pattern Unassigned, maybePartExpr, justPartExpr, joinMaybePE, mergePartial, mkPE)
 import           What4.Utils.MonadST
@@ -926,6 +927,12 @@ data MirStmt :: (CrucibleType -> Type) -> CrucibleType -> Type where
      !(TypeRepr tp) ->
      !(f (MirReferenceType tp)) ->
      MirStmt f (StructType (EmptyCtx ::> MirReferenceType (MirVectorType tp) ::> UsizeType))
+  -- | Get the index of a reference within its containing allocation, along
+  -- with the length of the allocation, as in `mirRef_indexAndLenIO`.  The
+  -- result is Nothing if the allocation has no fixed length.
+  MirRef_IndexAndLen ::
+     !(f (MirReferenceType tp)) ->
+     MirStmt f (MaybeType (StructType (EmptyCtx ::> UsizeType ::> UsizeType)))
   VectorSnoc ::
      !(TypeRepr tp) ->
      !(f (VectorType tp)) ->
@@ -984,6 +991,18 @@ data MirStmt :: (CrucibleType -> Type) -> CrucibleType -> Type where
     !(f (MirVectorType tp)) ->
     !(f UsizeType) ->
     MirStmt f (MirVectorType tp)
+  -- | Round a float to an integral value in the same format, as in
+  -- `f64::floor`.  Crucible's `App` has no operation for this.
+  MirFloatRound ::
+    !(FloatInfoRepr fi) ->
+    !RoundingMode ->
+    !(f (FloatType fi)) ->
+    MirStmt f (FloatType fi)
+  -- | Check whether a `usize` is concrete.  The result is always concrete, so
+  -- branching on it doesn't fork the execution.
+  MirUsizeIsConcrete ::
+    !(f UsizeType) ->
+    MirStmt f BoolType
 
 $(return [])
 
@@ -1004,6 +1023,7 @@ instance TestEqualityFC MirStmt where
        , (U.ConType [t|Index|] `U.TypeApp` U.AnyType `U.TypeApp` U.AnyType, [|testEquality|])
        , (U.ConType [t|GlobalVar|] `U.TypeApp` U.AnyType, [|testEquality|])
        , (U.ConType [t|NatRepr|] `U.TypeApp` U.AnyType, [|testEquality|])
+       , (U.ConType [t|FloatInfoRepr|] `U.TypeApp` U.AnyType, [|testEquality|])
        , (U.ConType [t|Assignment|] `U.TypeApp` U.AnyType `U.TypeApp` U.AnyType, [|testEquality|])
        ])
 instance TestEquality f => TestEquality (MirStmt f) where
@@ -1019,6 +1039,7 @@ instance OrdFC MirStmt where
        , (U.ConType [t|Index|] `U.TypeApp` U.AnyType `U.TypeApp` U.AnyType, [|compareF|])
        , (U.ConType [t|GlobalVar|] `U.TypeApp` U.AnyType, [|compareF|])
        , (U.ConType [t|NatRepr|] `U.TypeApp` U.AnyType, [|compareF|])
+       , (U.ConType [t|FloatInfoRepr|] `U.TypeApp` U.AnyType, [|compareF|])
        , (U.ConType [t|Assignment|] `U.TypeApp` U.AnyType `U.TypeApp` U.AnyType, [|compareF|])
        ])
 
@@ -1043,6 +1064,7 @@ instance TypeApp MirStmt where
     MirRef_OffsetWrap tp _ _ -> MirReferenceRepr tp
     MirRef_TryOffsetFrom _ _ -> MaybeRepr IsizeRepr
     MirRef_PeelIndex tp _ -> StructRepr (Empty :> MirReferenceRepr (MirVectorRepr tp) :> UsizeRepr)
+    MirRef_IndexAndLen _ -> MaybeRepr (StructRepr (Empty :> UsizeRepr :> UsizeRepr))
     VectorSnoc tp _ _ -> VectorRepr tp
     VectorHead tp _ -> MaybeRepr tp
     VectorTail tp _ -> VectorRepr tp
@@ -1056,6 +1078,8 @@ instance TypeApp MirStmt where
     MirVector_FromVector tp _ -> MirVectorRepr tp
     MirVector_FromArray btp _ -> MirVectorRepr (baseToType btp)
     MirVector_Resize tp _ _ -> MirVectorRepr tp
+    MirFloatRound fi _ _ -> FloatRepr fi
+    MirUsizeIsConcrete _ -> BoolRepr
 
 instance PrettyApp MirStmt where
   ppApp pp = \case
@@ -1078,6 +1102,7 @@ instance PrettyApp MirStmt where
     MirRef_OffsetWrap _ p o -> "mirRef_offsetWrap" <+> pp p <+> pp o
     MirRef_TryOffsetFrom p o -> "mirRef_tryOffsetFrom" <+> pp p <+> pp o
     MirRef_PeelIndex _ p -> "mirRef_peelIndex" <+> pp p
+    MirRef_IndexAndLen p -> "mirRef_indexAndLen" <+> pp p
     VectorSnoc _ v e -> "vectorSnoc" <+> pp v <+> pp e
     VectorHead _ v -> "vectorHead" <+> pp v
     VectorTail _ v -> "vectorTail" <+> pp v
@@ -1091,6 +1116,8 @@ instance PrettyApp MirStmt where
     MirVector_FromVector tp v -> "mirVector_fromVector" <+> pretty tp <+> pp v
     MirVector_FromArray btp a -> "mirVector_fromArray" <+> pretty btp <+> pp a
     MirVector_Resize _ v i -> "mirVector_resize" <+> pp v <+> pp i
+    MirFloatRound _ rm x -> "mirFloatRound" <+> viaShow rm <+> pp x
+    MirUsizeIsConcrete x -> "mirUsizeIsConcrete" <+> pp x
 
 
 instance FunctorFC MirStmt where
@@ -1510,15 +1537,34 @@ mirRef_overlapsIO bak (MirReferenceMux r1) (MirReferenceMux r2) =
 mirRef_offsetLeaf ::
     (IsSymBackend sym bak) =>
     bak ->
+    SimState p sym ext rtp f a ->
     TypeRepr tp ->
     MirReference sym tp ->
     RegValue sym IsizeType ->
     MuxLeafT sym IO (MirReference sym tp)
--- TODO: `offset` has a number of preconditions that we should check here:
+-- TODO: `offset` has more preconditions that we should check here:
 -- * addition must not overflow
--- * resulting pointer must be in-bounds for the allocation
 -- * total offset in bytes must not exceed isize::MAX
-mirRef_offsetLeaf = mirRef_offsetWrapLeaf
+mirRef_offsetLeaf bak s tpr ref@(MirReference root (Index_RefPath _ path _)) offset = do
+    let sym = backendGetSym bak
+    ref' <- mirRef_offsetWrapLeaf bak tpr ref offset
+    -- The result must be in bounds for the allocation, or one past its end.
+    -- Since the index is unsigned, this also rules out offsets before the
+    -- start.  Arrays of unbounded length can't be checked.
+    parentVec <- readMirRefLeaf s bak (MirReference root path)
+    let mbLen = case parentVec of
+            MirVector_Vector v -> Just $ V.length v
+            MirVector_PartialVector pv -> Just $ V.length pv
+            MirVector_Array _ -> Nothing
+    case (ref', mbLen) of
+        (MirReference _ (Index_RefPath _ _ idx'), Just lenInt) -> do
+            len <- liftIO $ bvLit sym knownNat $ BV.mkBV knownNat $ fromIntegral lenInt
+            inBounds <- liftIO $ bvUle sym idx' len
+            leafAssert bak inBounds $ GenericSimError $
+                "pointer offset out of bounds: the allocation has length " ++ show lenInt
+        _ -> return ()
+    return ref'
+mirRef_offsetLeaf bak _ tpr ref offset = mirRef_offsetWrapLeaf bak tpr ref offset
 
 mirRef_offsetWrapLeaf ::
     (IsSymBackend sym bak) =>
@@ -1698,7 +1744,7 @@ execMirStmt stmt s = withBackend ctx $ \bak ->
        MirRef_Eq (regValue -> MirReferenceMux r1) (regValue -> MirReferenceMux r2) ->
          readOnly s $ zipFancyMuxTrees' bak (mirRef_eqLeaf sym) (itePred sym) r1 r2
        MirRef_Offset tpr (regValue -> ref) (regValue -> off) ->
-         readOnly s $ modifyRefMux bak (\ref' -> mirRef_offsetLeaf bak tpr ref' off) ref
+         readOnly s $ modifyRefMux bak (\ref' -> mirRef_offsetLeaf bak s tpr ref' off) ref
        MirRef_OffsetWrap tpr (regValue -> ref) (regValue -> off) ->
          readOnly s $ modifyRefMux bak (\ref' -> mirRef_offsetWrapLeaf bak tpr ref' off) ref
        MirRef_TryOffsetFrom (regValue -> MirReferenceMux r1) (regValue -> MirReferenceMux r2) ->
@@ -1707,6 +1753,9 @@ execMirStmt stmt s = withBackend ctx $ \bak ->
        MirRef_PeelIndex tpr (regValue -> MirReferenceMux ref) -> do
          let tpr' = StructRepr (Empty :> MirReferenceRepr (MirVectorRepr tpr) :> IsizeRepr)
          readOnly s $ readFancyMuxTree' bak (mirRef_peelIndexIO sym tpr) (mux tpr') ref
+       MirRef_IndexAndLen (regValue -> ref) ->
+         readOnly s $ fmap (\(idx, len) -> Empty :> RV idx :> RV len) <$>
+            mirRef_indexAndLenIO bak s ref
 
        VectorSnoc _tp (regValue -> vecValue) (regValue -> elemValue) ->
             return (V.snoc vecValue elemValue, s)
@@ -1760,6 +1809,11 @@ execMirStmt stmt s = withBackend ctx $ \bak ->
                     "Attempted to resize MirVector backed by symbolic array"
             let pv' = V.generate (fromInteger newLen) getter
             return (MirVector_PartialVector pv', s)
+       MirFloatRound (_ :: FloatInfoRepr fi) rm (regValue -> x) -> do
+            val <- iFloatRound @_ @fi sym rm x
+            return (val, s)
+       MirUsizeIsConcrete (regValue -> x) ->
+            return (if Maybe.isJust (asBV x) then truePred sym else falsePred sym, s)
   where
     ctx = s^.stateContext
     iTypes = ctxIntrinsicTypes ctx
@@ -37,18 +37,23 @@ module Mir.Language (
 import qualified Data.Aeson as Aeson
 import qualified Data.BitVector.Sized as BV
 import qualified Data.Char       as Char
+import           GHC.Float (double2Float)
+import qualified LibBF           as BF
 import           Data.Functor.Const (Const(..))
 import           Control.Monad
 import           Control.Monad.IO.Class
+import           Data.IORef (IORef, newIORef, modifyIORef')
 import qualified Data.List       as List
 import           Data.Text (Text)
 import qualified Data.Text       as Text
 import           Data.Type.Equality ((:~:)(..),TestEquality(..))
 import qualified Data.Map.Strict as Map
 import           Data.Maybe (fromMaybe)
+import           Data.Word (Word64)
 import qualified Data.Sequence   as Seq
+import qualified Data.Set        as Set
 import qualified Data.Vector     as Vector
-import           Control.Lens ((^.), (^?), (^..), ix, each)
+import           Control.Lens ((^.), (^?), (^..), (&), (.~), ix, each)
 import           GHC.Generics (Generic)
 
 import System.Console.ANSI
@@ -70,11 +75,15 @@ import qualified Data.Parameterized.TraversableFC as Ctx
 
 -- crucible
 import qualified Lang.Crucible.Simulator               as C
+import qualified Lang.Crucible.Simulator.BoundedExec   as C
+import qualified Lang.Crucible.Simulator.EvalStmt      as C (GenericExecutionFeature(..), ExecutionFeatureResult(..))
+import qualified Lang.Crucible.Simulator.SimError      as C (SimError(..), SimErrorReason(..), simErrorReasonMsg)
 import qualified Lang.Crucible.CFG.Core                as C
 import qualified Lang.Crucible.FunctionHandle          as C
 import qualified Lang.Crucible.Backend                 as C
 
 -- what4
+import qualified What4.Concrete                        as W4C
 import qualified What4.Expr.Builder                    as W4
 import qualified What4.Interface                       as W4
 import qualified What4.Config                          as W4
@@ -96,7 +105,9 @@ import Crux.Log
 -- concurrency
 import Crucibles.DPOR
 import Crucibles.Explore
+import Crucibles.ExploreTypes (threadLocalVars)
 import Cruces.ExploreCrux
+import qualified Cruces.Log as Cruces
 
 -- crux-mir
 import           Mir.Mir
@@ -134,21 +145,25 @@ mainWithOutputTo h = mainWithOutputConfig $
 
 data MirLogging
     = LoggingCrux Crux.CruxLogMessage
+    | LoggingCruces Cruces.CrucesLogMessage
     | LoggingMir Log.MirLogMessage
     deriving (Generic, Aeson.ToJSON)
 
 mirLoggingToSayWhat :: MirLogging -> SayWhat
 mirLoggingToSayWhat (LoggingCrux msg) = Log.cruxLogMessageToSayWhat msg
+mirLoggingToSayWhat (LoggingCruces msg) = Cruces.crucesLogMessageToSayWhat msg
 mirLoggingToSayWhat (LoggingMir msg) = Log.mirLogMessageToSayWhat msg
 
 withMirLogging ::
     (
         ( Log.SupportsCruxLogMessage MirLogging
+        , Cruces.SupportsCrucesLogMessage MirLogging
         , Log.SupportsMirLogMessage MirLogging
         ) => computation
     ) -> computation
 withMirLogging computation =
     let ?injectCruxLogMessage = LoggingCrux
+        ?injectCrucesLogMessage = LoggingCruces
         ?injectMirLogMessage = LoggingMir
      in computation
 
@@ -192,6 +207,7 @@ data SomeTestOvr sym ctx (ty :: C.CrucibleType) =
 runTests ::
     Crux.Logs msgs =>
     Crux.SupportsCruxLogMessage msgs =>
+    Cruces.SupportsCrucesLogMessage msgs =>
     Log.SupportsMirLogMessage msgs =>
     (Crux.CruxOptions, MIROptions) -> IO ExitCode
 runTests opts = runTestsWithExtraOverrides noExtraOverrides opts
@@ -199,6 +215,7 @@ runTests opts = runTestsWithExtraOverrides noExtraOverrides opts
 runTestsWithExtraOverrides ::
     Crux.Logs msgs =>
     Crux.SupportsCruxLogMessage msgs =>
+    Cruces.SupportsCrucesLogMessage msgs =>
     Log.SupportsMirLogMessage msgs =>
     BindExtraOverridesFn ->
     (Crux.CruxOptions, MIROptions) ->
@@ -209,6 +226,9 @@ runTestsWithExtraOverrides bindExtra (cruxOpts, mirOpts) = do
     let ?assertFalseOnError = True
     let ?printCrucible      = printCrucible mirOpts
     let ?defaultRlibsDir    = defaultRlibsDir mirOpts
+    let ?overflowChecks     = not (noOverflowChecks mirOpts)
+    let ?dataRaces          = dataRaces mirOpts && concurrency mirOpts
+    let ?checkAssumptions   = checkAssumptions mirOpts
 
     let (filename, nameFilter) = case cargoTestFile mirOpts of
             -- This case is terrible a hack.  The goal is to mimic the behavior
@@ -257,15 +277,22 @@ runTestsWithExtraOverrides bindExtra (cruxOpts, mirOpts) = do
            $ "BUG: static initializer should not require arguments"
 
     let cfgMap = mir^.rmCFGs
+    let loopBounds = unrollBounds col
+    let preemptBounds = preemptionBounds col
 
     -- Simulate each test case
     let linkOverrides :: (C.IsSymInterface sym, sym ~ W4.ExprBuilder t st fs) =>
-            Maybe (Crux.SomeOnlineSolver sym bak) -> C.OverrideSim (p sym) sym MIR rtp a r ()
-        linkOverrides symOnline =
+            Maybe (Crux.SomeOnlineSolver sym bak) -> PathCounters -> C.OverrideSim (p sym) sym MIR rtp a r ()
+        linkOverrides symOnline counters =
             forM_ (Map.toList cfgMap) $ \(fn, C.AnyCFG cfg) -> do
                 case bindExtra symOnline (mir ^. rmCS) fn cfg of
                     Just f -> f
-                    Nothing -> bindFn symOnline (mir ^. rmCS) fn cfg
+                    Nothing
+                      | fn == normDefId "crucible::stats::branches_explored" ->
+                        bindCounter "branches_explored" (pcBranches counters) cfg
+                      | fn == normDefId "crucible::stats::paths_aborted" ->
+                        bindCounter "paths_aborted" (pcAborted counters) cfg
+                      | otherwise -> bindFn symOnline (mir ^. rmCS) fn cfg
     let entry = W4.mkProgramLoc "<entry>" W4.InternalPos
     let testStartLoc fnName =
             W4.mkProgramLoc (W4.functionNameFromText $ idText fnName) (W4.OtherPos "<start>")
@@ -284,17 +311,17 @@ runTestsWithExtraOverrides bindExtra (cruxOpts, mirOpts) = do
     -- that calls `simTest`.  Counterexamples are printed separately, and only
     -- for tests that failed.
 
-    let ?bound = 0
     let simTestBody :: forall sym bak p t st fs.
             ( C.IsSymBackend sym bak
             , sym ~ W4.ExprBuilder t st fs
             ) =>
             bak ->
             Maybe (Crux.SomeOnlineSolver sym bak) ->
+            PathCounters ->
             DefId ->
             Fun p sym MIR Ctx.EmptyCtx C.UnitType
-        simTestBody bak symOnline fnName =
-          do linkOverrides symOnline
+        simTestBody bak symOnline counters fnName =
+          do linkOverrides symOnline counters
              _ <- C.callCFG staticInitCfg C.emptyRegMap
 
              -- Label the current path for later use
@@ -325,6 +352,12 @@ runTestsWithExtraOverrides bindExtra (cruxOpts, mirOpts) = do
                  str <- showRegEntry @sym col resTy res
                  liftIO $ output $ "returned " ++ str ++ ", "
 
+    -- Each thread has its own unwinding state, since a panic unwinds only the
+    -- thread that panicked.
+    let unwindGlobals = case mir ^. rmCS . unwindVars of
+          Just uv -> [C.Some (uvCatchDepth uv), C.Some (uvUnwinding uv)]
+          Nothing -> []
+
     let printTest :: DefId -> Fun p sym ext args C.UnitType
         printTest fnName =
           when (not $ printResultOnly mirOpts) $
@@ -335,22 +368,26 @@ runTestsWithExtraOverrides bindExtra (cruxOpts, mirOpts) = do
             , sym ~ W4.ExprBuilder t st fs
             , Logs msgs
             , Log.SupportsCruxLogMessage msgs
+            , Cruces.SupportsCrucesLogMessage msgs
             , Log.SupportsMirLogMessage msgs
             ) =>
             bak ->
             Maybe (Crux.SomeOnlineSolver sym bak) ->
+            PathCounters ->
             DefId ->
             SomeTestOvr sym Ctx.EmptyCtx C.UnitType
-        simTest bak symOnline fnName
-          | concurrency mirOpts = SomeTestOvr
+        simTest bak symOnline counters fnName
+          | concurrency mirOpts =
+            let ?bound = maybe 0 fromIntegral (Map.lookup (idText fnName) preemptBounds) in
+            SomeTestOvr
             { testOvr = do printTest fnName
-                           exploreOvr bak symOnline cruxOpts $ simTestBody bak symOnline fnName
+                           exploreOvr bak symOnline cruxOpts $ simTestBody bak symOnline counters fnName
             , testFeatures = [scheduleFeature mirExplorePrimitives []]
-            , testPersonality = emptyExploration @DPOR
+            , testPersonality = emptyExploration @DPOR & threadLocalVars .~ unwindGlobals
             }
           | otherwise = SomeTestOvr
             { testOvr = do printTest fnName
-                           simTestBody bak symOnline fnName
+                           simTestBody bak symOnline counters fnName
             , testFeatures = []
             , testPersonality = Crux.CruxPersonality
             }
@@ -360,9 +397,16 @@ runTestsWithExtraOverrides bindExtra (cruxOpts, mirOpts) = do
             return $
               Crux.SimulatorHooks
                 { Crux.setupHook =
-                    \bak symOnline ->
-                      case simTest bak symOnline fnName of
+                    \bak symOnline -> do
+                      counters <- PathCounters <$> newIORef 0 <*> newIORef 0
+                      case simTest bak symOnline counters fnName of
                         SomeTestOvr testFn features personality -> do
+                          let statsFeatures = [C.genericToExecutionFeature (countPaths counters)]
+                          boundFeatures <- if Map.null loopBounds then return [] else do
+                              let getBound (C.SomeHandle h) = return $
+                                      Map.lookup (W4.functionName (C.handleName h)) loopBounds
+                              bf <- C.boundedExecFeature getBound True
+                              return [C.genericToExecutionFeature bf]
                           let outH = view outputHandle ?outputConfig
                           let sym = C.backendGetSym bak
                           setSimulatorVerbosity (Crux.simVerbose (Crux.outputOptions cruxOpts)) sym
@@ -370,7 +414,7 @@ runTestsWithExtraOverrides bindExtra (cruxOpts, mirOpts) = do
                                   (C.FnBindings C.emptyHandleMap) mirExtImpl personality
                           return (Crux.RunnableStateWithExtensions
                                   (C.InitialState simCtx C.emptyGlobals C.defaultAbortHandler C.UnitRepr $
-                                   C.runOverrideSim C.UnitRepr $ testFn) features
+                                   C.runOverrideSim C.UnitRepr $ testFn) (features ++ boundFeatures ++ statsFeatures)
                                  )
                 , Crux.onErrorHook = \_bak -> return (\_ _ -> return mempty)
                 , Crux.resultHook = \_bak result -> return result
@@ -416,16 +460,44 @@ runTestsWithExtraOverrides bindExtra (cruxOpts, mirOpts) = do
             sum (fmap (provedGoals . fst) gls) == sum (fmap (totalProcessedGoals . fst) gls)
     let anyFailed = any (not . isResultOK) results
 
-    let printCounterexamples gs = case gs of
-            Branch g1 g2 -> printCounterexamples g1 >> printCounterexamples g2
-            ProvedGoal{} -> return ()
-            NotProvedGoal _ _ _ _ Nothing _ -> return ()
-            NotProvedGoal _ _ _ _ (Just (m,_evs)) _ -> do
+    -- With `--distinct-failures`, only the first counterexample for each
+    -- failure kind, location, and message is printed.  The message of a
+    -- `crucible_assert!` is ignored, since it can differ between paths (for
+    -- example, when it's formatted with symbolic values).  Other messages,
+    -- such as those of the compiler's overflow and division checks, are fixed
+    -- strings that tell apart different checks at the same location.  `seen`
+    -- holds the failures printed so far and the number of counterexamples
+    -- skipped.
+    let failureKind reason = case reason of
+            C.GenericSimError _ -> 0 :: Int
+            C.Unsupported _ _ -> 1
+            C.ReadBeforeWriteSimError _ -> 2
+            C.AssertFailureSimError _ _ -> 3
+            C.ResourceExhausted _ -> 4
+    let failureMsg reason = case reason of
+            C.AssertFailureSimError msg _
+              | "MIR assertion at " `List.isPrefixOf` msg -> Nothing
+            _ -> Just (C.simErrorReasonMsg reason)
+    let failureKey err =
+            ( show (W4.plSourceLoc (C.simErrorLoc err))
+            , failureKind (C.simErrorReason err)
+            , failureMsg (C.simErrorReason err) )
+    let printCounterexamples seen@(printed, skipped) gs = case gs of
+            Branch g1 g2 -> do
+               seen' <- printCounterexamples seen g1
+               printCounterexamples seen' g2
+            ProvedGoal{} -> return seen
+            NotProvedGoal _ _ _ _ Nothing _ -> return seen
+            NotProvedGoal _ err _ _ (Just (m,_evs)) _
+              | distinctFailures mirOpts && failureKey err `Set.member` printed ->
+                return (printed, skipped + 1)
+              | otherwise -> do
                logGoal gs
                when (showModel mirOpts) $ do
                   outputLn "Model:"
                   mjs <- Crux.modelJS m
                   outputLn (Crux.renderJS mjs)
+               return (Set.insert (failureKey err) printed, skipped)
 
     when anyFailed $ do
         outputLn ""
@@ -434,7 +506,11 @@ runTestsWithExtraOverrides bindExtra (cruxOpts, mirOpts) = do
             when (not $ isResultOK res) $ do
                 outputLn ""
                 outputLn $ "---- " ++ show fnName ++ " counterexamples ----"
-                mapM_ (printCounterexamples . snd) $ cruxSimResultGoals res
+                (_, skipped) <- foldM printCounterexamples (Set.empty, 0 :: Int) $
+                    fmap snd $ cruxSimResultGoals res
+                when (skipped > 0) $
+                    outputLn $ "[Crux] " ++ show skipped ++
+                        " more counterexamples for the same failures are not shown"
 
     -- Print final tally of proved/disproved goals (except if
     -- --print-result-only is set)
@@ -458,6 +534,49 @@ runTestsWithExtraOverrides bindExtra (cruxOpts, mirOpts) = do
 
 
 
+-- | The counts behind `crucible::path_stats`, kept up to date by `countPaths`.
+data PathCounters = PathCounters
+    { pcBranches :: IORef Integer
+      -- ^ Branches on symbolic conditions, for `crucible::branches_explored`.
+    , pcAborted :: IORef Integer
+      -- ^ Paths that ended by aborting, for `crucible::stats::paths_aborted`.
+    }
+
+-- | An execution feature that counts the branches on symbolic conditions and
+-- the paths that abort.  It runs after the path satisfiability check, so a
+-- branch with only one feasible side isn't counted.
+countPaths :: PathCounters -> C.GenericExecutionFeature sym
+countPaths counters = C.GenericExecutionFeature $ \st -> do
+    case st of
+        C.SymbolicBranchState{} -> modifyIORef' (pcBranches counters) (+ 1)
+        C.AbortState{} -> modifyIORef' (pcAborted counters) (+ 1)
+        _ -> return ()
+    return C.ExecutionFeatureNoChange
+
+-- | Find calls to `crucible::unroll(n)`, and map the name of each function
+-- containing one to its loop bound `n`.  The call itself does nothing at run
+-- time.
+unrollBounds :: Collection -> Map.Map Text Word64
+unrollBounds = markerCalls ["crucible", "unroll"]
+
+-- | Find calls to `crucible::max_preemptions(n)`, and map the name of each
+-- test containing one to its preemption bound `n`.
+preemptionBounds :: Collection -> Map.Map Text Word64
+preemptionBounds = markerCalls ["crucible", "max_preemptions"]
+
+-- | Find calls to the marker function with the given path, passing a single
+-- integer literal, and map the name of each function containing one to the
+-- literal.
+markerCalls :: [Text] -> Collection -> Map.Map Text Word64
+markerCalls path col = Map.fromList
+    [ (idText (fn ^. fname), fromInteger (fromIntegerLit lit))
+    | fn <- Map.elems (col ^. functions)
+    , bb <- fn ^. fbody . mblocks
+    , Call (OpConstant (Constant _ (ConstFunction f))) [OpConstant (Constant _ (ConstInt lit))] _ _
+        <- [bb ^. bbdata . bbterminator]
+    , idKey f == path
+    ]
+
 data MIROptions = MIROptions
     { onlyPP       :: Bool
     , printCrucible :: Bool
@@ -470,6 +589,20 @@ data MIROptions = MIROptions
     -- | Generate test overrides that recognize concurrency primitives
     -- and attempt to explore all interleaving executions
     , concurrency :: Bool
+    -- | With `concurrency`, report a data race when two threads access the
+    -- same memory through raw pointers without synchronizing
+    , dataRaces :: Bool
+    -- | Compile the program without arithmetic overflow checks, so that
+    -- overflow wraps silently as in a release build.
+    , noOverflowChecks :: Bool
+    -- | When a test fails, print only one counterexample for each distinct
+    -- failure, identified by its source location, kind, and message.  The
+    -- messages of `crucible_assert!` are ignored.
+    , distinctFailures :: Bool
+    -- | Ask the solver whether each `crucible_assume!` can hold on the
+    -- current path, and warn if it can't.  Without this, only an assumption
+    -- that is constant `false` is reported.
+    , checkAssumptions :: Bool
     , testFilter   :: Maybe Text
     , cargoTestFile :: Maybe FilePath
     , defaultRlibsDir :: FilePath
@@ -486,6 +619,10 @@ defaultMirOptions = MIROptions
     , showModel = False
     , assertFalse = False
     , concurrency = False
+    , dataRaces = False
+    , noOverflowChecks = False
+    , distinctFailures = False
+    , checkAssumptions = False
     , printResultOnly = False
     , testFilter = Nothing
     , cargoTestFile = Nothing
@@ -521,6 +658,22 @@ mirConfig = Crux.Config
             "run with support for concurrency primitives"
             (GetOpt.NoArg (\opts -> Right opts { concurrency = True }))
 
+        , GetOpt.Option [] ["data-races"]
+            "with --concurrency, report unsynchronized accesses through raw pointers as data races"
+            (GetOpt.NoArg (\opts -> Right opts { dataRaces = True }))
+
+        , GetOpt.Option [] ["no-overflow-checks"]
+            "let integer arithmetic wrap on overflow instead of reporting an error"
+            (GetOpt.NoArg (\opts -> Right opts { noOverflowChecks = True }))
+
+        , GetOpt.Option [] ["distinct-failures"]
+            "print one counterexample for each failing location and message, instead of one per failing goal"
+            (GetOpt.NoArg (\opts -> Right opts { distinctFailures = True }))
+
+        , GetOpt.Option [] ["check-assumptions"]
+            "warn when a crucible_assume! can't hold on the current path, checking each one with the solver"
+            (GetOpt.NoArg (\opts -> Right opts { checkAssumptions = True }))
+
         , GetOpt.Option []  ["test-filter"]
             "run only tests whose names contain this string"
             (GetOpt.ReqArg "string" (\v opts -> Right opts { testFilter = Just $ Text.pack v }))
@@ -577,9 +730,9 @@ showRegEntry col mty (C.RegEntry tp rv) =
     (TyUint _sz, C.BVRepr _w) -> return $ case W4.asBV rv of
                      Just i  -> show (BV.asUnsigned i)
                      Nothing -> "Symbolic BV"
-    (TyFloat _,  C.RealValRepr) -> return $ case W4.asRational rv of
-                     Just f -> show f
-                     Nothing -> "Symbolic real"
+    (TyFloat fk, C.FloatRepr _) -> return $ case W4.asConcrete rv of
+                     Just (W4C.ConcreteFloat _ bf) -> showFloat fk bf
+                     _ -> "Symbolic float"
 
     (TyTuple [], C.UnitRepr) -> return "()"
 
@@ -739,6 +892,18 @@ showRegEntry col mty (C.RegEntry tp rv) =
     readField (FieldRepr (FkMaybe tpr)) (W4.Err _) =
         error $ "readField: W4.Err for type " ++ show tpr
 
+-- | Show a concrete float the way Rust's `{:?}` does for the common cases.
+showFloat :: FloatKind -> BF.BigFloat -> String
+showFloat fk bf
+  | BF.bfIsNaN bf = "NaN"
+  | BF.bfIsInf bf = if BF.bfIsNeg bf then "-inf" else "inf"
+  | otherwise = case fk of
+      F32 -> show (double2Float d)
+      F64 -> show d
+  where
+    -- Every `f32` is exactly representable as a `Double`.
+    (d, _) = BF.bfToDouble BF.NearEven bf
+
 
 data FoundVariant sym ctx tp where
     FoundVariant ::
@@ -21,6 +21,7 @@ import qualified Crux.Log as Log
 
 data MirLogMessage
   = FinalResults
+  | UnsatisfiableAssumption T.Text T.Text
   deriving (Generic, ToJSON)
 
 type SupportsMirLogMessage msgs =
@@ -41,5 +42,12 @@ cruxMirLogTag = "Crux-MIR"
 simply :: T.Text -> SayWhat
 simply = SayWhat Simply cruxMirLogTag
 
+warn :: T.Text -> SayWhat
+warn = SayWhat Warn cruxMirLogTag
+
 mirLogMessageToSayWhat :: MirLogMessage -> SayWhat
 mirLogMessageToSayWhat FinalResults = simply "---- FINAL RESULTS ----"
+mirLogMessageToSayWhat (UnsatisfiableAssumption loc src) =
+  warn $
+    "Assumption at " <> loc <> " can't hold on this path, "
+      <> "so later assertions on it pass vacuously: " <> src
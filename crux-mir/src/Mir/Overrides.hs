@@ -11,7 +11,7 @@
 {-# Language PartialTypeSignatures #-}
 {-# Language FlexibleContexts #-}
 
-module Mir.Overrides (bindFn, getString) where
+module Mir.Overrides (bindFn, bindCounter, getString) where
 
 import Control.Exception (throwIO)
 import Control.Lens ((^.), (^?), (%=), (.=), use, ix, _Wrapped)
@@ -25,6 +25,7 @@ import Data.BitVector.Sized (mkBV)
 import qualified Data.BitVector.Sized as BV
 import qualified Data.ByteString as BS
 import qualified Data.Char as Char
+import Data.IORef (IORef, readIORef)
 import Data.Map (Map, fromList)
 import qualified Data.Map as Map
 import Data.Maybe (fromMaybe, catMaybes)
@@ -51,11 +52,12 @@ import qualified What4.Expr.ArrayUpdateMap as AUM
 import What4.Expr.GroundEval (GroundValue, GroundEvalFn(..), GroundArray(..))
 import What4.FunctionName (FunctionName, functionNameFromText)
 import What4.Interface
+import What4.InterpretedFloatingPoint (iFloatBaseTypeRepr)
 import What4.LabeledPred (LabeledPred(..))
 import What4.Partial (PartExpr, pattern PE, pattern Unassigned, justPartExpr)
 import What4.Protocol.Online
     ( OnlineSolver, inNewFrame, solverEvalFuns , solverConn, check
-    , getUnsatCore , checkWithAssumptionsAndModel )
+    , getUnsatCore , checkWithAssumptions, checkWithAssumptionsAndModel )
 import What4.Protocol.SMTWriter
     ( mkFormula, assumeFormulaWithFreshName , assumeFormula
     , smtExprGroundEvalFn )
@@ -65,8 +67,9 @@ import Lang.Crucible.Analysis.Postdom (postdomInfo)
 import Lang.Crucible.Backend
     ( CrucibleAssumption(..), IsSymBackend, LabeledPred(..), addAssumption
     , assert, getPathCondition, Assumption(..), addFailedAssertion, IsSymInterface
-    , singleEvent, addAssumptions, CrucibleEvent(..), backendGetSym
-    , throwUnsupported )
+    , singleEvent, addAssumptions, CrucibleEvent(..), CrucibleAssumptions(..), backendGetSym
+    , throwUnsupported, collectAssumptions, flattenAssumptions
+    , getProofObligations, goalsToList )
 import Lang.Crucible.Backend.Online
 import Lang.Crucible.CFG.Core (CFG, cfgArgTypes, cfgHandle, cfgReturnType, lastReg)
 import Lang.Crucible.FunctionHandle
@@ -83,12 +86,14 @@ import Lang.Crucible.Utils.MuxTree
 
 
 import Crux (SomeOnlineSolver(..))
+import Crux.Log (Logs)
 import Crux.Types (Vals(..), Entry(..))
 
 import Mir.DefId
 import Mir.FancyMuxTree
 import Mir.Generator (CollectionState, collection, handleMap, MirHandle(..))
 import Mir.Intrinsics
+import qualified Mir.Log as Log
 import qualified Mir.Mir as M
 
 
@@ -110,6 +115,21 @@ getString (Empty :> RV mirPtr :> RV lenExpr) = runMaybeT $ do
   where
     readBV e = MaybeT (return (BV.asUnsigned <$> asBV e))
 
+-- | Build a string slice with the given (concrete) contents.
+makeString :: forall sym rtp args ret p. (IsSymInterface sym) =>
+    Text ->
+    OverrideSim p sym MIR rtp args ret (RegValue sym (MirSlice (BVType 8)))
+makeString str = do
+    sym <- getSymInterface
+    let bytes = BS.unpack $ Text.encodeUtf8 str
+    elems <- liftIO $ mapM (bvLit sym knownNat . BV.word8) bytes
+    let vec = MirVector_Vector $ V.fromList elems
+    let vecRef = newConstMirRef sym (MirVectorRepr (BVRepr (knownNat @8))) vec
+    ptr <- subindexMirRefSim (BVRepr (knownNat @8)) vecRef =<<
+        liftIO (bvLit sym knownRepr (BV.zero knownRepr))
+    len <- liftIO $ bvLit sym knownRepr (BV.mkBV knownRepr (toInteger (length bytes)))
+    return $ Empty :> RV ptr :> RV len
+
 data SomeOverride p sym where
   SomeOverride :: CtxRepr args -> TypeRepr ret -> Override p sym MIR args ret -> SomeOverride p sym
 
@@ -124,7 +144,12 @@ makeSymbolicVar nameReg btpr = ovrWithBackend $ \bak -> do
     name <- case nameOpt of
         Just x -> return $ Text.unpack x
         Nothing -> fail "symbolic variable name must be a concrete string"
-    nameSymbol <- case userSymbol name of
+    -- Names like `a[0]` and `t.0` (see `crucible::symbolic::indexed_name` and
+    -- `field_name`) aren't valid solver symbols, so other characters are
+    -- replaced with underscores.  The original name is still used when
+    -- printing counterexamples.
+    let safeName = map (\c -> if Char.isAlphaNum c then c else '_') name
+    nameSymbol <- case userSymbol safeName of
         Left err -> fail $ "invalid symbolic variable name " ++ show name ++ ": " ++ show err
         Right x -> return x
     v <- liftIO $ freshConstant sym nameSymbol btpr
@@ -133,6 +158,14 @@ makeSymbolicVar nameReg btpr = ovrWithBackend $ \bak -> do
     liftIO $ addAssumptions bak (singleEvent ev)
     return v
 
+-- | The events recorded in some assumptions, including those on both sides of
+-- a merged branch.
+assumptionEvents :: CrucibleAssumptions e -> [CrucibleEvent e]
+assumptionEvents (SingleEvent e) = [e]
+assumptionEvents (SingleAssumption _) = []
+assumptionEvents (ManyAssumptions as) = concatMap assumptionEvents as
+assumptionEvents (MergeAssumptions _ xs ys) = assumptionEvents xs ++ assumptionEvents ys
+
 array_symbolic ::
   forall sym rtp btp p .
   (IsSymInterface sym) =>
@@ -144,6 +177,18 @@ array_symbolic btpr = do
     RegMap (Empty :> nameReg) <- getOverrideArgs
     makeSymbolicVar nameReg $ BaseArrayRepr (Empty :> BaseUsizeRepr) btpr
 
+-- | A fresh bitvector of width `w`, for `crucible::bitvector::make_symbolic`.
+bv_symbolic ::
+  forall sym rtp w p .
+  (IsSymInterface sym, 1 <= w) =>
+  NatRepr w ->
+  OverrideSim (p sym) sym MIR rtp
+    (EmptyCtx ::> MirSlice (BVType 8)) (BVType w)
+    (RegValue sym (BVType w))
+bv_symbolic w = do
+    RegMap (Empty :> nameReg) <- getOverrideArgs
+    makeSymbolicVar nameReg $ BaseBVRepr w
+
 concretize ::
   forall sym bak rtp tp p .
   (IsSymInterface sym) =>
@@ -180,6 +225,31 @@ concretize (Just (SomeOnlineSolver bak)) = do
 
 concretize Nothing = fail "`concretize` requires an online solver backend"
 
+-- | Check whether an assumption of `p` can't hold on the current path.  Without
+-- an online solver, only a `p` that is syntactically false is detected.
+assumptionUnsat ::
+  IsSymInterface sym =>
+  Maybe (SomeOnlineSolver sym bak) ->
+  Pred sym ->
+  IO Bool
+assumptionUnsat symOnline p = case (asConstantPred p, symOnline) of
+    (Just b, _) -> return (not b)
+    (Nothing, Nothing) -> return False
+    (Nothing, Just (SomeOnlineSolver bak)) -> do
+        let sym = backendGetSym bak
+        -- As in `concretize`, enable online solving for the check.
+        enabledOpt <- getOptionSetting enableOnlineBackend (getConfiguration sym)
+        wasEnabled <- getOpt enabledOpt
+        _ <- setOpt enabledOpt True
+        unsat <- withSolverProcess bak (return False) $ \sp -> do
+            cond <- getPathCondition bak
+            result <- checkWithAssumptions sp "assumption" [cond, p]
+            return $ case result of
+                Unsat _ -> True
+                _ -> False
+        _ <- setOpt enabledOpt wasEnabled
+        return unsat
+
 groundExpr ::
     IsExprBuilder sym =>
     sym ->
@@ -413,9 +483,30 @@ overrideRust cs name = do
     regValue <$> callFnVal (HandleFnVal fhG) args
 
 
-bindFn ::
-  forall p ng args ret blocks sym bak rtp a r.
+-- | Bind one of the `crucible::stats` counting hooks, such as
+-- `branches_explored`, to an override that reads the count from `ref`, which
+-- the simulator's execution features keep up to date.
+bindCounter ::
   (IsSymInterface sym) =>
+  FunctionName ->
+  IORef Integer ->
+  CFG MIR blocks args ret ->
+  OverrideSim (p sym) sym MIR rtp a r ()
+bindCounter name ref cfg
+  | Empty <- cfgArgTypes cfg
+  , UsizeRepr <- cfgReturnType cfg
+  = bindFnHandle (cfgHandle cfg) $ UseOverride $
+    mkOverride' name UsizeRepr $ do
+        sym <- getSymInterface
+        n <- liftIO $ readIORef ref
+        liftIO $ bvLit sym knownNat (BV.mkBV knownNat n)
+  | otherwise = error $ "bad signature for crucible::stats::" ++ show name ++ ": " ++
+      show (cfgArgTypes cfg, cfgReturnType cfg)
+
+
+bindFn ::
+  forall p ng args ret blocks sym bak rtp a r msgs.
+  (IsSymInterface sym, Logs msgs, Log.SupportsMirLogMessage msgs, ?checkAssumptions::Bool) =>
   Maybe (SomeOnlineSolver sym bak) ->
   CollectionState ->
   Text ->
@@ -429,6 +520,13 @@ bindFn symOnline cs name cfg
   = bindFnHandle (cfgHandle cfg) $ UseOverride $
     mkOverride' "array::symbolic" (UsizeArrayRepr btpr) (array_symbolic btpr)
 
+  | (normDefId "crucible::bitvector::make_symbolic" <> "::_inst") `Text.isPrefixOf` name
+  , Empty :> MirSliceRepr (BVRepr w) <- cfgArgTypes cfg
+  , BVRepr w' <- cfgReturnType cfg
+  , Just Refl <- testEquality w (knownNat @8)
+  = bindFnHandle (cfgHandle cfg) $ UseOverride $
+    mkOverride' "bitvector::make_symbolic" (BVRepr w') (bv_symbolic w')
+
   | (normDefId "crucible::concretize" <> "::_inst") `Text.isPrefixOf` name
   , Empty :> tpr <- cfgArgTypes cfg
   , Just Refl <- testEquality tpr (cfgReturnType cfg)
@@ -453,7 +551,7 @@ bindFn symOnline cs name cfg
         liftIO $ putStrLn $ Text.unpack str ++ " = " ++ show (printSymExpr expr)
 
 
-bindFn _symOnline _cs fn cfg =
+bindFn symOnline _cs fn cfg =
   ovrWithBackend $ \bak ->
   let s = backendGetSym bak in
   case Map.lookup fn (overrides bak) of
@@ -495,9 +593,48 @@ bindFn _symOnline _cs fn cfg =
       do RegMap (Empty :> str) <- getOverrideArgs
          makeSymbolicVar str $ BaseBVRepr n
 
+    symb_float :: forall fi . Text -> FloatInfoRepr fi -> (Text, FunctionName -> SomeOverride (p sym) sym)
+    symb_float name fi =
+      override name (Empty :> strrepr) (FloatRepr fi) $
+      do RegMap (Empty :> str) <- getOverrideArgs
+         sym <- getSymInterface
+         makeSymbolicVar str $ iFloatBaseTypeRepr sym fi
+
     overrides :: IsSymBackend sym bak' => bak' -> Map Text (FunctionName -> SomeOverride (p sym) sym)
     overrides bak =
-      let sym = backendGetSym bak in
+      let sym = backendGetSym bak
+
+          -- Also used by libcore's models, e.g. of `binary_search`.  With
+          -- `warnUnsat`, warns if the assumption can't hold on the current
+          -- path.
+          assume_impl warnUnsat name =
+            let argTys = (Empty :> BoolRepr :> strrepr :> strrepr :> u32repr :> u32repr)
+            in override name argTys UnitRepr $
+               do RegMap (Empty :> c :> srcArg :> fileArg :> lineArg :> colArg) <- getOverrideArgs
+                  loc <- liftIO $ getCurrentProgramLoc sym
+                  src <- maybe (fail "not a constant src string")
+                           (pure . Text.unpack)
+                           =<< getString (regValue srcArg)
+                  file <- maybe (fail "not a constant filename string") pure =<< getString (regValue fileArg)
+                  line <- maybe (fail "not a constant line number") pure
+                          (BV.asUnsigned <$> asBV (regValue lineArg))
+                  col <- maybe (fail "not a constant column number") pure
+                         (BV.asUnsigned <$> asBV (regValue colArg))
+                  let locStr = Text.unpack file <> ":" <> show line <> ":" <> show col
+                  let reason = GenericAssumption loc ("Assumption \n\t" <> src <> "\nfrom " <> locStr) (regValue c)
+                  -- An assumption that can't hold on this path makes every
+                  -- later assertion on the path vacuous, which usually
+                  -- means the test is over-constrained.  Asking the solver
+                  -- costs a query per assumption, so without
+                  -- `--check-assumptions` only a constant `false` is caught.
+                  when warnUnsat $ liftIO $
+                    do let checkOnline = if ?checkAssumptions then symOnline else Nothing
+                       unsat <- assumptionUnsat checkOnline (regValue c)
+                       when unsat $ Log.sayMir $
+                         Log.UnsatisfiableAssumption (Text.pack locStr) (Text.pack src)
+                  liftIO $ addAssumption bak reason
+                  return ()
+      in
       fromList [ override "crucible::one" Empty (BVRepr (knownNat @8)) $
                  do h <- printHandle <$> getContext
                     liftIO (hPutStrLn h "Hello, I'm an override")
@@ -508,10 +645,46 @@ bindFn _symOnline _cs fn cfg =
                , symb_bv "crucible::symbolic::symbolic_u32" (knownNat @32)
                , symb_bv "crucible::symbolic::symbolic_u64" (knownNat @64)
                , symb_bv "crucible::symbolic::symbolic_u128" (knownNat @128)
+               , symb_bv "core::crucible::symbolic::symbolic_usize" (knownNat @SizeBits)
+               , symb_float "crucible::symbolic::symbolic_f32" SingleFloatRepr
+               , symb_float "crucible::symbolic::symbolic_f64" DoubleFloatRepr
+               , override "crucible::symbolic::indexed_name" (Empty :> strrepr :> UsizeRepr) strrepr $
+                 do RegMap (Empty :> descArg :> idxArg) <- getOverrideArgs
+                    desc <- maybe (fail "symbolic variable name must be a concrete string") pure
+                            =<< getString (regValue descArg)
+                    idx <- maybe (fail "symbolic array index must be concrete") pure
+                           (BV.asUnsigned <$> asBV (regValue idxArg))
+                    makeString $ desc <> "[" <> Text.pack (show idx) <> "]"
+               , override "crucible::symbolic::field_name" (Empty :> strrepr :> UsizeRepr) strrepr $
+                 do RegMap (Empty :> descArg :> idxArg) <- getOverrideArgs
+                    desc <- maybe (fail "symbolic variable name must be a concrete string") pure
+                            =<< getString (regValue descArg)
+                    idx <- maybe (fail "symbolic tuple index must be concrete") pure
+                           (BV.asUnsigned <$> asBV (regValue idxArg))
+                    makeString $ desc <> "." <> Text.pack (show idx)
+               , override "crucible::symbolic::concrete_bits" (Empty :> strrepr :> BVRepr (knownNat @128)) UnitRepr $
+                 do RegMap (Empty :> descArg :> bitsArg) <- getOverrideArgs
+                    name <- maybe (fail "symbolic variable name must be a concrete string") (pure . Text.unpack)
+                            =<< getString (regValue descArg)
+                    bits <- maybe (fail "the value of a pinned variable must be concrete") pure
+                            (BV.asUnsigned <$> asBV (regValue bitsArg))
+                    evs <- liftIO $ assumptionEvents <$> collectAssumptions bak
+                    case [ ev | ev@(CreateVariableEvent _ nm _ _) <- evs, nm == name ] of
+                      [CreateVariableEvent _ _ tpr v] ->
+                        do p <- case tpr of
+                                  BaseBVRepr w -> liftIO $ bvEq sym v =<< bvLit sym w (BV.mkBV w bits)
+                                  _ -> fail $ "symbolic variable " ++ show name ++ " can't be pinned to a concrete value"
+                           loc <- liftIO $ getCurrentProgramLoc sym
+                           liftIO $ addAssumption bak (GenericAssumption loc ("concrete value for " <> name) p)
+                      [] -> fail $ "no symbolic variable named " ++ show name
+                      _ -> fail $ "more than one symbolic variable is named " ++ show name
+               , override "crucible::stats::assumption_count" Empty UsizeRepr $
+                 do n <- liftIO $ length <$> (flattenAssumptions sym =<< collectAssumptions bak)
+                    liftIO $ bvLit sym knownNat (BV.mkBV knownNat (toInteger n))
+               , override "crucible::stats::assertion_count" Empty UsizeRepr $
+                 do n <- liftIO $ maybe 0 (length . goalsToList) <$> getProofObligations bak
+                    liftIO $ bvLit sym knownNat (BV.mkBV knownNat (toInteger n))
                , symb_bv "int512::symbolic" (knownNat @512)
-               , symb_bv "crucible::bitvector::make_symbolic_128" (knownNat @128)
-               , symb_bv "crucible::bitvector::make_symbolic_256" (knownNat @256)
-               , symb_bv "crucible::bitvector::make_symbolic_512" (knownNat @512)
 
                , let argTys = (Empty :> BoolRepr :> strrepr :> strrepr :> u32repr :> u32repr)
                  in override "crucible::crucible_assert_impl" argTys UnitRepr $
@@ -528,20 +701,9 @@ bindFn _symOnline _cs fn cfg =
                        let reason = AssertFailureSimError ("MIR assertion at " <> locStr <> ":\n\t" <> src) ""
                        liftIO $ assert bak (regValue c) reason
                        return ()
-               , let argTys = (Empty :> BoolRepr :> strrepr :> strrepr :> u32repr :> u32repr)
-                 in override "crucible::crucible_assume_impl" argTys UnitRepr $
-                    do RegMap (Empty :> c :> srcArg :> fileArg :> lineArg :> colArg) <- getOverrideArgs
-                       loc <- liftIO $ getCurrentProgramLoc sym
-                       src <- maybe (fail "not a constant src string")
-                                (pure . Text.unpack)
-                                =<< getString (regValue srcArg)
-                       file <- maybe (fail "not a constant filename string") pure =<< getString (regValue fileArg)
-                       line <- maybe (fail "not a constant line number") pure
-                               (BV.asUnsigned <$> asBV (regValue lineArg))
-                       col <- maybe (fail "not a constant column number") pure
-                              (BV.asUnsigned <$> asBV (regValue colArg))
-                       let locStr = Text.unpack file <> ":" <> show line <> ":" <> show col
-                       let reason = GenericAssumption loc ("Assumption \n\t" <> src <> "\nfrom " <> locStr) (regValue c)
-                       liftIO $ addAssumption bak reason
-                       return ()
+               , assume_impl True "crucible::crucible_assume_impl"
+               , assume_impl False "crucible::crucible_assume_unreachable_impl"
+               -- The models in libcore make many assumptions, and checking
+               -- each of them with the solver would be too slow.
+               , assume_impl False "core::crucible::symbolic::assume_impl"
                ]
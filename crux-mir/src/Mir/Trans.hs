@@ -36,6 +36,8 @@ module Mir.Trans(transCollection,transStatics,RustModule(..)
                 , vectorCopy, ptrCopy
                 , evalRval
                 , callExp
+                , callHandle
+                , evalOperand
                 , derefExp, readPlace, addrOfPlace
                 , initialValue
                 , eBVLit
@@ -75,6 +77,7 @@ import qualified Lang.Crucible.FunctionHandle as FH
 import qualified What4.ProgramLoc as PL
 import qualified What4.FunctionName as FN
 import qualified What4.Utils.StringLiteral as W4
+import qualified What4.Interface as W4 (RoundingMode(..))
 import qualified Lang.Crucible.CFG.Reg as R
 import qualified Lang.Crucible.CFG.SSAConversion as SSA
 import qualified Lang.Crucible.CFG.Expr as E
@@ -96,6 +99,7 @@ import qualified Mir.DefId as M
 
 import Mir.Intrinsics
 import Mir.Generator
+import Mir.Concurrency (dataAccessName)
 import Mir.GenericOps
 import Mir.TransTy
 
@@ -181,11 +185,24 @@ transConstVal _ty (Some (C.BVRepr w)) (M.ConstChar c) =
 transConstVal _ty (Some C.UnitRepr) (M.ConstFunction _did) =
     return $ MirExp C.UnitRepr $ S.app E.EmptyApp
 
-transConstVal _ty (Some (C.RealValRepr)) (M.ConstFloat (M.FloatLit _ str)) =
-    case reads str of
-      (d , _):_ -> let rat = toRational (d :: Double) in
-                   return (MirExp C.RealValRepr (S.app $ E.RationalLit rat))
-      []        -> mirFail $ "cannot parse float constant: " ++ show str
+transConstVal _ty (Some (C.FloatRepr fi)) (M.ConstFloat (M.FloatLit _ str)) =
+    case parseFloatLit str of
+      Just d -> case fi of
+          C.SingleFloatRepr ->
+              return (MirExp (C.FloatRepr fi) (S.app $ E.FloatLit (realToFrac d)))
+          C.DoubleFloatRepr ->
+              return (MirExp (C.FloatRepr fi) (S.app $ E.DoubleLit d))
+          _ -> mirFail $ "unsupported float format for constant: " ++ show fi
+      Nothing -> mirFail $ "cannot parse float constant: " ++ show str
+  where
+    -- `reads` doesn't accept the spellings Rust uses for non-finite values
+    parseFloatLit :: String -> Maybe Double
+    parseFloatLit "NaN" = Just (0 / 0)
+    parseFloatLit "inf" = Just (1 / 0)
+    parseFloatLit "-inf" = Just (-1 / 0)
+    parseFloatLit s = case reads s of
+      (d, _):_ -> Just d
+      [] -> Nothing
 
 transConstVal _ty _ (ConstInitializer funid) =
     callExp funid []
@@ -296,8 +313,8 @@ getReturnExp tpr = do
 -- ** Expressions: Operations and Aggregates
 
 evalOperand :: HasCallStack => M.Operand -> MirGenerator h s ret (MirExp s)
-evalOperand (M.Copy lv) = evalPlace lv >>= readPlace
-evalOperand (M.Move lv) = evalPlace lv >>= readPlace
+evalOperand (M.Copy lv) = evalPlace lv >>= noteRead lv >>= readPlace
+evalOperand (M.Move lv) = evalPlace lv >>= noteRead lv >>= readPlace
 evalOperand (M.OpConstant (M.Constant conty constval)) = do
     Some tpr <- tyToReprM conty
     transConstVal conty (Some tpr) constval
@@ -464,23 +481,26 @@ evalBinOp bop mat me1 me2 =
             M.Beq -> return (MirExp C.BoolRepr (S.app $ E.Not $ S.app $ E.BoolXor e1 e2), noOverflow)
             M.Ne  -> return (MirExp C.BoolRepr (S.app $ E.BoolXor e1 e2), noOverflow)
             _ -> mirFail $ "No translation for bool binop: " ++ fmt bop
-      (MirExp C.RealValRepr e1, MirExp C.RealValRepr e2) ->
+      (MirExp (C.FloatRepr fi) e1, MirExp (C.FloatRepr fi') e2)
+        | Just Refl <- testEquality fi fi' ->
           case bop of
-            M.Beq -> return (MirExp C.BoolRepr (S.app $ E.RealEq e1 e2), noOverflow)
-            M.Lt -> return (MirExp C.BoolRepr (S.app $ E.RealLt e1 e2), noOverflow)
-            M.Le -> return (MirExp C.BoolRepr (S.app $ E.RealLe e1 e2), noOverflow)
-            M.Gt -> return (MirExp C.BoolRepr (S.app $ E.RealLt e2 e1), noOverflow)
-            M.Ge -> return (MirExp C.BoolRepr (S.app $ E.RealLe e2 e1), noOverflow)
-            M.Ne -> return (MirExp C.BoolRepr (S.app $ E.Not $ S.app $ E.RealEq e1 e2), noOverflow)
+            -- Comparisons follow IEEE 754: every comparison involving NaN is
+            -- false, except `!=`, which is true.
+            M.Beq -> return (MirExp C.BoolRepr (S.app $ E.FloatFpEq e1 e2), noOverflow)
+            M.Lt -> return (MirExp C.BoolRepr (S.app $ E.FloatLt e1 e2), noOverflow)
+            M.Le -> return (MirExp C.BoolRepr (S.app $ E.FloatLe e1 e2), noOverflow)
+            M.Gt -> return (MirExp C.BoolRepr (S.app $ E.FloatGt e1 e2), noOverflow)
+            M.Ge -> return (MirExp C.BoolRepr (S.app $ E.FloatGe e1 e2), noOverflow)
+            M.Ne -> return (MirExp C.BoolRepr (S.app $ E.Not $ S.app $ E.FloatFpEq e1 e2), noOverflow)
 
             -- Binops on floats never set the overflow flag
-            M.Add -> return (MirExp C.RealValRepr (S.app $ E.RealAdd e1 e2), noOverflow)
-            M.Sub -> return (MirExp C.RealValRepr (S.app $ E.RealSub e1 e2), noOverflow)
-            M.Mul -> return (MirExp C.RealValRepr (S.app $ E.RealMul e1 e2), noOverflow)
-            M.Div -> return (MirExp C.RealValRepr (S.app $ E.RealDiv e1 e2), noOverflow)
-            M.Rem -> return (MirExp C.RealValRepr (S.app $ E.RealMod e1 e2), noOverflow)
+            M.Add -> return (MirExp (C.FloatRepr fi) (S.app $ E.FloatAdd fi W4.RNE e1 e2), noOverflow)
+            M.Sub -> return (MirExp (C.FloatRepr fi) (S.app $ E.FloatSub fi W4.RNE e1 e2), noOverflow)
+            M.Mul -> return (MirExp (C.FloatRepr fi) (S.app $ E.FloatMul fi W4.RNE e1 e2), noOverflow)
+            M.Div -> return (MirExp (C.FloatRepr fi) (S.app $ E.FloatDiv fi W4.RNE e1 e2), noOverflow)
+            M.Rem -> return (MirExp (C.FloatRepr fi) (floatFmod fi e1 e2), noOverflow)
 
-            _ -> mirFail $ "No translation for real number binop: " ++ fmt bop
+            _ -> mirFail $ "No translation for float binop: " ++ fmt bop
 
       (MirExp (MirReferenceRepr tpr1) e1, MirExp (MirReferenceRepr tpr2) e2)
         | Just Refl <- testEquality tpr1 tpr2 ->
@@ -499,6 +519,22 @@ evalBinOp bop mat me1 me2 =
 
       (_, _) -> mirFail $ "bad or unimplemented type: " ++ (fmt bop) ++ ", " ++ (show me1) ++ ", " ++ (show me2)
 
+-- | Rust's `%` on floats, which truncates the quotient, like C's `fmod`.
+-- `FloatRem` is IEEE `remainder`, which rounds the quotient to nearest, so its
+-- result can have the opposite sign from `x`.  In that case, adding or
+-- subtracting `|y|` gives the `fmod` result, which is exact because that
+-- result is representable.  NaN, infinite, and zero operands need no fixup.
+floatFmod :: C.FloatInfoRepr fi -> R.Expr MIR s (C.FloatType fi) -> R.Expr MIR s (C.FloatType fi) ->
+    R.Expr MIR s (C.FloatType fi)
+floatFmod fi x y =
+    let r = S.app $ E.FloatRem fi x y
+        zero = S.app $ E.FloatPZero fi
+        absY = S.app $ E.FloatAbs fi y
+        up = S.app $ E.And (S.app $ E.FloatLt r zero) (S.app $ E.FloatIsPositive x)
+        down = S.app $ E.And (S.app $ E.FloatGt r zero) (S.app $ E.FloatIsNegative x)
+    in S.app $ E.FloatIte fi up (S.app $ E.FloatAdd fi W4.RNE r absY) $
+       S.app $ E.FloatIte fi down (S.app $ E.FloatSub fi W4.RNE r absY) r
+
   where
     noOverflow :: R.Expr MIR s C.BoolType
     noOverflow = S.app $ E.BoolLit False
@@ -580,6 +616,7 @@ transNullaryOp ::  M.NullOp -> M.Ty -> MirGenerator h s ret (MirExp s)
 transNullaryOp M.Box ty = do
     -- Box<T> has special translation to ensure that its representation is just
     -- an ordinary pointer.
+    noteAllocation
     Some tpr <- tyToReprM ty
     ptr <- newMirRef tpr
     maybeInitVal <- initialValue ty
@@ -602,7 +639,7 @@ transUnaryOp uop op = do
       (M.Not, MirExp (C.BVRepr n) e) -> return $ MirExp (C.BVRepr n) $ S.app $ E.BVNot n e
       (M.Neg, MirExp (C.BVRepr n) e) -> return $ MirExp (C.BVRepr n) (S.app $ E.BVSub n (S.app $ eBVLit n 0) e)
       (M.Neg, MirExp C.IntegerRepr e) -> return $ MirExp C.IntegerRepr $ S.app $ E.IntNeg e
-      (M.Neg, MirExp C.RealValRepr e) -> return $ MirExp C.RealValRepr $ S.app $ E.RealNeg e
+      (M.Neg, MirExp (C.FloatRepr fi) e) -> return $ MirExp (C.FloatRepr fi) $ S.app $ E.FloatNeg fi e
       (_ , MirExp ty e) -> mirFail $ "Unimplemented unary op `" ++ fmt uop ++ "' for " ++ show ty
 
 
@@ -730,10 +767,31 @@ evalCast' ck ty1 e ty2  = do
 
 
 
-{-      -- BV to Float
-      (M.Misc, M.TyInt bsz, TyFloat fsz)
-       | MirExp (C.BVRepr sz) e0 <- e
-       -> return $ MirExp C.FloatRepr -}
+      -- int to float (rounding to nearest)
+      (M.Misc, M.TyUint _, M.TyFloat fk)
+       | MirExp (C.BVRepr _) e0 <- e
+       -> floatKindToFloatInfoCont fk $ \fi ->
+           return $ MirExp (C.FloatRepr fi) (R.App $ E.FloatFromBV fi W4.RNE e0)
+      (M.Misc, M.TyInt _, M.TyFloat fk)
+       | MirExp (C.BVRepr _) e0 <- e
+       -> floatKindToFloatInfoCont fk $ \fi ->
+           return $ MirExp (C.FloatRepr fi) (R.App $ E.FloatFromSBV fi W4.RNE e0)
+
+      -- float to int (rounding toward zero, saturating, and NaN to 0)
+      (M.Misc, M.TyFloat _, M.TyUint bsz)
+       | MirExp (C.FloatRepr fi) e0 <- e
+       -> baseSizeToNatCont bsz $ \w ->
+           return $ MirExp (C.BVRepr w) (floatToBVSat False w fi e0)
+      (M.Misc, M.TyFloat _, M.TyInt bsz)
+       | MirExp (C.FloatRepr fi) e0 <- e
+       -> baseSizeToNatCont bsz $ \w ->
+           return $ MirExp (C.BVRepr w) (floatToBVSat True w fi e0)
+
+      -- float to float
+      (M.Misc, M.TyFloat _, M.TyFloat fk)
+       | MirExp (C.FloatRepr _) e0 <- e
+       -> floatKindToFloatInfoCont fk $ \fi ->
+           return $ MirExp (C.FloatRepr fi) (R.App $ E.FloatCast fi W4.RNE e0)
 
       -- Not sure why this appears in generated MIR, but libcore has some no-op
       -- unsizes from `*const dyn Any` to `*const dyn Any`
@@ -880,6 +938,29 @@ evalCast' ck ty1 e ty2  = do
         an2 = adt2 ^. adtname
 
 
+-- | Convert a float to a (signed or unsigned) integer of width `w` as Rust's
+-- `as` does: values are rounded toward zero, values out of the integer type's
+-- range (including the infinities) saturate to its minimum or maximum, and NaN
+-- becomes 0.  `FloatToBV` and `FloatToSBV` alone leave the result unspecified
+-- in those cases.
+floatToBVSat :: (1 <= w) =>
+    Bool -> NatRepr w -> C.FloatInfoRepr fi ->
+    R.Expr MIR s (C.FloatType fi) -> R.Expr MIR s (C.BVType w)
+floatToBVSat signed w fi e =
+    R.App $ E.BVIte (R.App $ E.FloatIsNaN e) w (R.App $ eBVLit w 0) $
+    R.App $ E.BVIte (R.App $ E.FloatLt e (bound lo)) w (R.App $ eBVLit w lo) $
+    R.App $ E.BVIte (R.App $ E.FloatGe e (bound hi)) w (R.App $ eBVLit w (hi - 1)) $
+    R.App $ if signed then E.FloatToSBV w W4.RTZ e else E.FloatToBV w W4.RTZ e
+  where
+    bits = fromIntegral $ intValue w
+    -- The range is `lo .. hi`.  Both bounds are powers of two (or zero), so
+    -- they're exact as floats, except that `2^128` rounds to infinity in
+    -- `f32`, which only infinity is greater than or equal to.
+    (lo, hi)
+      | signed = (negate $ 1 `shift` (bits - 1), 1 `shift` (bits - 1))
+      | otherwise = (0, 1 `shift` bits)
+    bound n = R.App $ E.FloatFromReal fi W4.RNE (R.App $ E.RationalLit (fromInteger n))
+
 evalCast :: HasCallStack => M.CastKind -> M.Operand -> M.Ty -> MirGenerator h s ret (MirExp s)
 evalCast ck op ty = do
     e <- evalOperand op
@@ -984,7 +1065,7 @@ evalRval rv@(M.RAdtAg (M.AdtAg adt agv ops ty)) = do
 
 
 evalLvalue :: HasCallStack => M.Lvalue -> MirGenerator h s ret (MirExp s)
-evalLvalue lv = evalPlace lv >>= readPlace
+evalLvalue lv = evalPlace lv >>= noteRead lv >>= readPlace
 
 
 evalPlace :: HasCallStack => M.Lvalue -> MirGenerator h s ret (MirPlace s)
@@ -1131,13 +1212,37 @@ doAssignCoerce lv ty exp =
 
 doAssign :: HasCallStack => M.Lvalue -> MirExp s -> MirGenerator h s ret ()
 doAssign lv (MirExp tpr val) = do
-    MirPlace tpr' ref _ <- evalPlace lv
+    pl@(MirPlace tpr' ref _) <- evalPlace lv
+    noteDataAccess True lv pl
     Refl <- testEqualityOrFail tpr tpr' $
         "ill-typed assignment of " ++ show tpr ++ " to " ++ show tpr'
             ++ " (" ++ show (M.typeOf lv) ++ ") " ++ show lv
     writeMirRef ref val
 
 
+noteRead :: M.Lvalue -> MirPlace s -> MirGenerator h s ret (MirPlace s)
+noteRead lv pl = noteDataAccess False lv pl >> return pl
+
+-- | When checking for data races, tell the scheduler about an access to `pl`,
+-- if `lv` goes through a raw pointer.  Other accesses can't race in safe Rust,
+-- and those in the standard library's synchronization primitives (atomics and
+-- `Mutex`) are modeled separately.
+noteDataAccess :: Bool -> M.Lvalue -> MirPlace s -> MirGenerator h s ret ()
+noteDataAccess isWrite lv (MirPlace tpr ref _) = do
+    mh <- use $ cs . dataAccessHandle
+    case mh of
+        Just h | throughRawPtr lv -> do
+            let arg = R.App $ E.PackAny (MirReferenceRepr tpr) ref
+            _ <- G.call (R.App $ E.HandleLit h)
+                (Ctx.Empty Ctx.:> R.App (E.BoolLit isWrite) Ctx.:> arg)
+            return ()
+        _ -> return ()
+  where
+    throughRawPtr (M.LBase _) = False
+    throughRawPtr (M.LProj lv' M.Deref)
+      | M.TyRawPtr _ _ <- M.typeOf lv' = True
+    throughRawPtr (M.LProj lv' _) = throughRawPtr lv'
+
 transStatement :: HasCallStack => M.Statement -> MirGenerator h s ret ()
 transStatement (M.Assign lv rv pos) = do
   col <- use $ cs . collection
@@ -1292,6 +1397,65 @@ doReturn tr = do
 
     G.returnFromFunction e
 
+-- | Run the first action inside `catch_unwind`, and the second outside it.
+-- The catch depth is always concrete, so this doesn't fork the execution.
+ifCatching ::
+    (forall b. MirGenerator h s ret b) ->
+    (forall b. MirGenerator h s ret b) ->
+    MirGenerator h s ret a
+ifCatching catching notCatching = use (cs . unwindVars) >>= \case
+    Nothing -> notCatching
+    Just uv -> do
+        depth <- G.readGlobal (uvCatchDepth uv)
+        catchingLbl <- G.defineBlockLabel catching
+        notCatchingLbl <- G.defineBlockLabel notCatching
+        G.branch (S.app $ usizeLt (S.app $ usizeLit 0) depth) catchingLbl notCatchingLbl
+
+-- | Panic with the given message.  Inside `catch_unwind`, this starts
+-- unwinding: it sets the unwinding flag, then leaves the function through its
+-- cleanup block, if it has one.  Outside, it reports an error.
+panicOrUnwind :: C.TypeRepr ret -> Maybe M.BasicBlockInfo -> R.Expr MIR s (C.StringType C.Unicode) ->
+    MirGenerator h s ret a
+panicOrUnwind tr cleanup msg = ifCatching
+    (do uv <- use (cs . unwindVars) >>= maybe (mirFail "panic: unwinding is not set up") return
+        G.writeGlobal (uvUnwinding uv) $ S.app $ E.BoolLit True
+        unwind tr cleanup)
+    (G.reportError msg)
+
+-- | After a call returns, keep unwinding if the callee was unwinding.
+checkUnwind :: C.TypeRepr ret -> Maybe M.BasicBlockInfo -> MirGenerator h s ret ()
+checkUnwind tr cleanup = use (cs . unwindVars) >>= \case
+    Nothing -> return ()
+    Just uv -> do
+        unwinding <- G.readGlobal (uvUnwinding uv)
+        G.ifte_ unwinding (unwind tr cleanup) (return ())
+
+unwind :: C.TypeRepr ret -> Maybe M.BasicBlockInfo -> MirGenerator h s ret a
+unwind _ (Just bb) = jumpToBlock bb
+unwind tr Nothing = returnUnwinding tr
+
+-- | Return from a function that is unwinding.
+returnUnwinding :: C.TypeRepr ret -> MirGenerator h s ret a
+returnUnwinding tr = do
+    retTy <- use $ currentFn . M.fsig . M.fsreturn_ty
+    e <- unwindingReturnValue tr retTy
+    cleanupLocals
+    G.returnFromFunction e
+
+-- | The caller of a function that is unwinding ignores its return value, which
+-- may not have been written yet, so the function returns a placeholder
+-- instead.  A null reference stands in for references, which `initialValue`
+-- doesn't handle.
+unwindingReturnValue :: C.TypeRepr ret -> M.Ty -> MirGenerator h s ret (R.Expr MIR s ret)
+unwindingReturnValue tr ty = initialValue ty >>= \case
+    Just (MirExp tpr e) | Just Refl <- testEquality tpr tr -> return e
+    _ -> case tr of
+        MirReferenceRepr tpr -> integerToMirRef tpr $ R.App $ usizeLit 0
+        MirSliceRepr tpr -> do
+            ptr <- integerToMirRef tpr $ R.App $ usizeLit 0
+            return $ mkSlice tpr ptr $ R.App $ usizeLit 0
+        _ -> getReturnExp tr
+
 ---------------------------------------------------------------------------------------------------
 ---------------------------------------------------------------------------------------------------
 -- | Find the function expression for this name (instantiated with the given type arguments)
@@ -1423,14 +1587,16 @@ callExp funid cargs = do
 
 -- regular function calls: closure calls & dynamic trait method calls handled later
 doCall :: forall h s ret a. (HasCallStack) => M.DefId -> [M.Operand]
-   -> Maybe (M.Lvalue, M.BasicBlockInfo) -> C.TypeRepr ret -> MirGenerator h s ret a
-doCall funid cargs cdest retRepr = do
+   -> Maybe (M.Lvalue, M.BasicBlockInfo) -> Maybe M.BasicBlockInfo -> C.TypeRepr ret
+   -> MirGenerator h s ret a
+doCall funid cargs cdest cleanup retRepr = do
     _am    <- use $ cs.collection
     db    <- use debugLevel
     isCustom <- resolveCustom funid
     case cdest of
       (Just (dest_lv, jdest)) -> do
             ret <- callExp funid cargs
+            checkUnwind retRepr cleanup
             doAssign dest_lv ret
             jumpToBlock jdest
 
@@ -1441,9 +1607,16 @@ doCall funid cargs cdest retRepr = do
                msg  <- op exps
                G.reportError (S.app $ E.StringLit $ W4.UnicodeLiteral msg)
 
+         | Just (CustomOpPanic op) <- isCustom -> do
+               exps <- mapM evalOperand cargs
+               msg  <- op exps
+               panicOrUnwind retRepr cleanup (S.app $ E.StringLit $ W4.UnicodeLiteral msg)
+
         -- other functions that don't return
         | otherwise -> do
             _ <- callExp funid cargs
+            -- A function that doesn't return can still unwind.
+            checkUnwind retRepr cleanup
             -- TODO: is this the correct behavior?
             G.reportError (S.app $ E.StringLit $ fromString "Program terminated.")
 
@@ -1463,13 +1636,14 @@ transTerminator (M.DropAndReplace dlv dop dtarg _ dropFn) _ = do
     transStatement (M.Assign dlv (M.Use dop) "<dummy pos>")
     jumpToBlock dtarg
 
-transTerminator (M.Call (M.OpConstant (M.Constant _ (M.ConstFunction funid))) cargs cretdest _) tr = do
+transTerminator (M.Call (M.OpConstant (M.Constant _ (M.ConstFunction funid))) cargs cretdest cleanup) tr = do
     isCustom <- resolveCustom funid
-    doCall funid cargs cretdest tr -- cleanup ignored
+    doCall funid cargs cretdest cleanup tr
 
-transTerminator (M.Call funcOp cargs cretdest _) tr = do
+transTerminator (M.Call funcOp cargs cretdest cleanup) tr = do
     func <- evalOperand funcOp
     ret <- callHandle func RustAbi Nothing cargs
+    checkUnwind tr cleanup
     case cretdest of
       Just (dest_lv, jdest) -> do
           doAssign dest_lv ret
@@ -1477,14 +1651,20 @@ transTerminator (M.Call funcOp cargs cretdest _) tr = do
       Nothing -> do
           G.reportError (S.app $ E.StringLit $ fromString "Program terminated.")
 
-transTerminator (M.Assert cond expected msg target _cleanup) _ = do
+transTerminator (M.Assert cond expected msg target cleanup) tr = do
     MirExp tpr e <- evalOperand cond
     Refl <- testEqualityOrFail tpr C.BoolRepr "expected Assert cond to be BoolType"
-    G.assertExpr (S.app $ E.BoolEq e (S.app $ E.BoolLit expected)) $
-        S.app $ E.StringLit $ W4.UnicodeLiteral $ msg
-    jumpToBlock target
+    let ok = S.app $ E.BoolEq e (S.app $ E.BoolLit expected)
+    let msg' = S.app $ E.StringLit $ W4.UnicodeLiteral $ msg
+    ifCatching
+        (do okLbl <- G.defineBlockLabel $ jumpToBlock target
+            failLbl <- G.defineBlockLabel $ panicOrUnwind tr cleanup msg'
+            G.branch ok okLbl failLbl)
+        (do G.assertExpr ok msg'
+            jumpToBlock target)
 transTerminator (M.Resume) tr =
-    doReturn tr -- resume happens when unwinding
+    -- Resume happens at the end of the cleanup code run when unwinding.
+    returnUnwinding tr
 transTerminator (M.Drop dlv dt _dunwind dropFn) _ = do
     let ptrOp = M.Temp $ M.Cast M.Misc
             (M.Temp $ M.AddressOf M.Mut dlv) (M.TyRawPtr (M.typeOf dlv) M.Mut)
@@ -2223,7 +2403,7 @@ mkDiscrMap col = mconcat
 transCollection ::
     (HasCallStack, ?debug::Int, ?assertFalseOnError::Bool,
      ?libCS::CollectionState, ?customOps::CustomOpMap,
-     ?printCrucible::Bool)
+     ?printCrucible::Bool, ?dataRaces::Bool)
     => M.Collection
     -> FH.HandleAllocator
     -> IO RustModule
@@ -2254,9 +2434,21 @@ transCollection col halloc = do
 
     let dm = mkDiscrMap col
 
+    dataAccess <- if ?dataRaces then Just <$> mkDataAccessCFG halloc else return Nothing
+
+    -- Only a program that can call `catch_unwind` needs the unwinding
+    -- globals.  Without them, panics fail right away, and calls aren't
+    -- followed by a check of the unwinding flag.
+    uv <- if usesTryCall col
+        then fmap Just $ UnwindVars
+            <$> G.freshGlobalVar halloc "catch_unwind_depth" UsizeRepr
+            <*> G.freshGlobalVar halloc "unwinding" C.BoolRepr
+        else return Nothing
+
+    ac <- G.freshGlobalVar halloc "allocation_count" UsizeRepr
 
     let colState :: CollectionState
-        colState = CollectionState hmap vm sm dm col
+        colState = CollectionState hmap vm sm dm col (fst <$> dataAccess) uv (Just ac)
 
     -- translate all of the functions
     fnInfo <- mapM (stToIO . transDefine (?libCS <> colState)) (Map.elems (col^.M.functions))
@@ -2270,12 +2462,40 @@ transCollection col halloc = do
                 (intr^.M.intrName) methodId dynTraitName methodIndex
         _ -> return Nothing) (Map.elems (col ^. M.intrinsics))
 
+    let pairs4 = [(dataAccessName, cfg) | Just (_, cfg) <- [dataAccess]]
+
     return $ RustModule
                 { _rmCS    = colState
-                , _rmCFGs  = Map.fromList (pairs1 <> concat pairs2 <> pairs3)
+                , _rmCFGs  = Map.fromList (pairs1 <> concat pairs2 <> pairs3 <> pairs4)
                 , _rmTransInfo = transInfo
                 }
 
+-- | Whether the collection calls `core::crucible::unwind::try_call`, on which
+-- `catch_unwind` is built.  It's generic, so each call is to an intrinsic
+-- instance of it.
+usesTryCall :: M.Collection -> Bool
+usesTryCall col = any isTryCall (col ^. M.intrinsics)
+  where
+    isTryCall intr =
+        M.idKey (intr ^. M.intrInst . M.inDefId) == ["core", "crucible", "unwind", "try_call"]
+
+-- | Build the function that `noteDataAccess` calls before each access through a
+-- raw pointer.  It does nothing here; the concurrency scheduler intercepts
+-- calls to it and checks the access for data races.
+mkDataAccessCFG ::
+    FH.HandleAllocator -> IO (FH.FnHandle DataAccessArgs C.UnitType, Core.AnyCFG MIR)
+mkDataAccessCFG halloc = do
+  let argCtx = Ctx.Empty Ctx.:> C.BoolRepr Ctx.:> C.AnyRepr
+  h <- FH.mkHandle' halloc (FN.functionNameFromText dataAccessName) argCtx C.UnitRepr
+  let def :: G.FunctionDef MIR (Const ()) DataAccessArgs C.UnitType (ST w)
+      def _inputs = (Const (), return (R.App $ E.EmptyApp))
+  cfg <- stToIO $ do
+    ng <- newSTNonceGenerator
+    (R.SomeCFG g, []) <- G.defineFunction PL.InternalPos ng h def
+    case SSA.toSSA g of
+        Core.SomeCFG g_ssa -> return (Core.AnyCFG g_ssa)
+  return (h, cfg)
+
 -- | Produce a crucible CFG that initializes the global variables for the static
 -- part of the crate
 transStatics :: CollectionState -> FH.HandleAllocator -> IO (Core.AnyCFG MIR)
@@ -2304,7 +2524,12 @@ transStatics colState halloc = do
   let def :: G.FunctionDef MIR (Const ()) Ctx.EmptyCtx C.UnitType (ST w)
       def inputs = (s, f) where
           s = Const ()
-          f = do mapM_ initializeStatic (colState^.collection.statics)
+          f = do forM_ (colState^.unwindVars) $ \uv -> do
+                   G.writeGlobal (uvCatchDepth uv) $ R.App $ usizeLit 0
+                   G.writeGlobal (uvUnwinding uv) $ R.App $ E.BoolLit False
+                 forM_ (colState^.allocCount) $ \ac ->
+                   G.writeGlobal ac $ R.App $ usizeLit 0
+                 mapM_ initializeStatic (colState^.collection.statics)
                  return (R.App $ E.EmptyApp)
   init_cfg <- stToIO $ do
     ng <- newSTNonceGenerator
@@ -2361,15 +2586,37 @@ ptrCopy ::
     G.Expr MIR s UsizeType ->
     MirGenerator h s ret ()
 ptrCopy tpr src dest len = do
+    -- The loop runs over every element that the source and destination have
+    -- room for, copying only those below `len`.  The number of iterations is
+    -- usually concrete even when `len` is symbolic, so a symbolic `len` costs
+    -- one branch per element, merged right away, instead of a separate path
+    -- for each possible length.  Without a fixed-length allocation to bound
+    -- it, or if `len` is concrete, the loop runs to `len`.
+    srcAvail <- mirRef_available src
+    destAvail <- mirRef_available dest
+    bound <- G.caseMaybe srcAvail UsizeRepr $ G.MatchMaybe
+        (\n -> do G.assertExpr (S.app $ usizeLe len n) $
+                      S.litExpr "source out of bounds in copy"
+                  return n)
+        (return len)
+    bound' <- G.caseMaybe destAvail UsizeRepr $ G.MatchMaybe
+        (\n -> do G.assertExpr (S.app $ usizeLe len n) $
+                      S.litExpr "destination out of bounds in copy"
+                  return $ S.app $ E.BVIte (S.app $ usizeLt n bound) knownRepr n bound)
+        (return bound)
+    lenConcrete <- usizeIsConcrete len
+    let bound'' = S.app $ E.BVIte lenConcrete knownRepr len bound'
+
     iRef <- G.newRef $ S.app $ usizeLit 0
     let pos = PL.InternalPos
     G.while (pos, do i <- G.readRef iRef
-                     return (G.App $ usizeLt i len))
+                     return (G.App $ usizeLt i bound''))
             (pos, do i <- G.readRef iRef
-                     src' <- mirRef_offset tpr src i
-                     dest' <- mirRef_offset tpr dest i
-                     val <- readMirRef tpr src'
-                     writeMirRef dest' val
+                     G.whenCond (G.App $ usizeLt i len) $ do
+                         src' <- mirRef_offset tpr src i
+                         dest' <- mirRef_offset tpr dest i
+                         val <- readMirRef tpr src'
+                         writeMirRef dest' val
                      let i' = S.app $ usizeAdd i (S.app $ usizeLit 1)
                      G.writeRef iRef i')
     G.dropRef iRef
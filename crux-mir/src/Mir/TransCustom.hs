@@ -54,6 +54,7 @@ import qualified Lang.Crucible.CFG.Expr as E
 import qualified Lang.Crucible.Syntax as S
 import qualified Lang.Crucible.CFG.Reg as R
 
+import qualified What4.Interface as W4
 import qualified What4.ProgramLoc as PL
 
 
@@ -90,6 +91,7 @@ customOpDefs = Map.fromList $ [
                          , discriminant_value
                          , type_id
                          , mem_swap
+                         , mem_replace
                          , add_with_overflow
                          , sub_with_overflow
                          , mul_with_overflow
@@ -98,6 +100,7 @@ customOpDefs = Map.fromList $ [
                          , wrapping_mul
                          , saturating_add
                          , saturating_sub
+                         , saturating_mul
                          , unchecked_add
                          , unchecked_sub
                          , unchecked_mul
@@ -147,6 +150,8 @@ customOpDefs = Map.fromList $ [
                          , ptr_offset_from
                          , ptr_offset_from_mut
                          , ptr_compare_usize
+                         , ptr_len_bound
+                         , symbolic_bool_is_concrete
                          , is_aligned_and_not_null
                          , ptr_slice_from_raw_parts
                          , ptr_slice_from_raw_parts_mut
@@ -163,10 +168,13 @@ customOpDefs = Map.fromList $ [
                          , panicking_panic
                          , panicking_panic_fmt
                          , panicking_panicking
+                         , unwind_try_call
+                         , unwind_start_thread
 
                          , allocate
                          , allocate_zeroed
                          , reallocate
+                         , allocation_count
 
                          , maybe_uninit_uninit
 
@@ -184,7 +192,7 @@ customOpDefs = Map.fromList $ [
                          , integer_rem
                          , integer_eq
                          , integer_lt
-                         ] ++ bv_funcs ++ atomic_funcs
+                         ] ++ bv_funcs ++ atomic_funcs ++ float_funcs
 
 
  
@@ -200,26 +208,60 @@ abort = (["core", "intrinsics", "", "abort"], \s ->
             Just (CustomOpExit $ \ops -> return "intrinsics::abort"))
 
 panicking_begin_panic :: (ExplodedDefId, CustomRHS)
-panicking_begin_panic = (["std", "panicking", "begin_panic"], \s -> Just $ CustomOpExit $ \ops -> do
+panicking_begin_panic = (["std", "panicking", "begin_panic"], \s -> Just $ CustomOpPanic $ \ops -> do
     name <- use $ currentFn . fname
     return $ "panicking::begin_panic, called from " <> M.idText name
     )
 
 panicking_panic :: (ExplodedDefId, CustomRHS)
-panicking_panic = (["core", "panicking", "panic"], \s -> Just $ CustomOpExit $ \ops -> do
+panicking_panic = (["core", "panicking", "panic"], \s -> Just $ CustomOpPanic $ \ops -> do
     name <- use $ currentFn . fname
     return $ "panicking::panic, called from " <> M.idText name
     )
 
 panicking_panic_fmt :: (ExplodedDefId, CustomRHS)
-panicking_panic_fmt = (["core", "panicking", "panic_fmt"], \s -> Just $ CustomOpExit $ \ops -> do
+panicking_panic_fmt = (["core", "panicking", "panic_fmt"], \s -> Just $ CustomOpPanic $ \ops -> do
     name <- use $ currentFn . fname
     return $ "panicking::panic_fmt, called from " <> M.idText name
     )
 
+-- | `thread::panicking` is true while unwinding from a panic inside
+-- `catch_unwind`, such as in the destructors run by the unwinding.
 panicking_panicking :: (ExplodedDefId, CustomRHS)
 panicking_panicking = (["std", "panicking", "panicking"], \_ -> Just $ CustomOp $ \_ _ -> do
-    return $ MirExp C.BoolRepr $ R.App $ E.BoolLit False)
+    muv <- use $ cs . unwindVars
+    case muv of
+        Just uv -> MirExp C.BoolRepr <$> G.readGlobal (uvUnwinding uv)
+        Nothing -> return $ MirExp C.BoolRepr $ R.App $ E.BoolLit False)
+
+-- | `try_call(f, data)` calls `f(data)`, catching any panic, and returns
+-- whether `f` panicked.  While `f` runs, panics unwind back to here instead
+-- of failing.  `try_call` itself doesn't check for unwinding after the call,
+-- so unwinding stops here.
+unwind_try_call :: (ExplodedDefId, CustomRHS)
+unwind_try_call = (["core", "crucible", "unwind", "try_call"], \_ -> Just $ CustomMirOp $ \ops -> case ops of
+    [fnOp, dataOp] -> do
+        uv <- use (cs . unwindVars) >>= maybe (mirFail "try_call: unwinding is not set up") return
+        depth <- G.readGlobal (uvCatchDepth uv)
+        G.writeGlobal (uvCatchDepth uv) $ R.App $ usizeAdd depth $ R.App $ usizeLit 1
+        f <- evalOperand fnOp
+        _ <- callHandle f RustAbi Nothing [dataOp]
+        G.writeGlobal (uvCatchDepth uv) depth
+        panicked <- G.readGlobal (uvUnwinding uv)
+        G.writeGlobal (uvUnwinding uv) $ R.App $ E.BoolLit False
+        return $ MirExp C.BoolRepr panicked
+    _ -> mirFail $ "bad arguments to try_call: " ++ show ops)
+
+-- | A new thread starts outside of any `catch_unwind`.  Under `--concurrency`,
+-- the unwinding globals are thread-local, but a new thread starts with the
+-- values of the thread that ran before it, so they're reset here.
+unwind_start_thread :: (ExplodedDefId, CustomRHS)
+unwind_start_thread = (["core", "crucible", "unwind", "start_thread"], \_ -> Just $ CustomOp $ \_ _ -> do
+    muv <- use $ cs . unwindVars
+    forM_ muv $ \uv -> do
+        G.writeGlobal (uvCatchDepth uv) $ R.App $ usizeLit 0
+        G.writeGlobal (uvUnwinding uv) $ R.App $ E.BoolLit False
+    return $ MirExp C.UnitRepr $ R.App E.EmptyApp)
 
 
 -----------------------------------------------------------------------------------------------------
@@ -503,6 +545,25 @@ ptr_compare_usize = (["core", "crucible", "ptr", "compare_usize"],
             _ -> mirFail $ "bad arguments for ptr::compare_usize: " ++ show ops
         _ -> Nothing)
 
+ptr_len_bound :: (ExplodedDefId, CustomRHS)
+ptr_len_bound = (["core", "crucible", "ptr", "len_bound"],
+    \_substs -> Just $ CustomOp $ \_ ops -> case ops of
+        [MirExp (MirReferenceRepr _) ptr, MirExp UsizeRepr len] -> do
+            avail <- mirRef_available ptr
+            lenConcrete <- usizeIsConcrete len
+            MirExp UsizeRepr <$> G.caseMaybe avail UsizeRepr (G.MatchMaybe
+                (\n -> return $ R.App $ usizeIte lenConcrete len n)
+                (return len))
+        _ -> mirFail $ "bad arguments for ptr::len_bound: " ++ show ops)
+
+symbolic_bool_is_concrete :: (ExplodedDefId, CustomRHS)
+symbolic_bool_is_concrete = (["core", "crucible", "symbolic", "bool_is_concrete"],
+    \_substs -> Just $ CustomOp $ \_ ops -> case ops of
+        [MirExp C.BoolRepr b] ->
+            MirExp C.BoolRepr <$> usizeIsConcrete
+                (R.App $ usizeIte b (R.App $ usizeLit 1) (R.App $ usizeLit 0))
+        _ -> mirFail $ "bad arguments for symbolic::bool_is_concrete: " ++ show ops)
+
 is_aligned_and_not_null :: (ExplodedDefId, CustomRHS)
 -- Not an actual intrinsic, so it's not in an `extern` block, so it doesn't
 -- have the "" element in its path.
@@ -724,6 +785,7 @@ saturateValueSigned :: (1 <= w) => NatRepr w -> BinOp -> R.Expr MIR s C.BoolType
 saturateValueSigned w op pos = case op of
     Add -> Just $ R.App $ E.BVIte pos w maxVal minVal
     Sub -> Just $ R.App $ E.BVIte pos w minVal maxVal
+    Mul -> Just $ R.App $ E.BVIte pos w maxVal minVal
     _ -> Nothing
   where
     bits = fromIntegral $ C.intValue w
@@ -745,7 +807,10 @@ makeSaturatingArith name bop =
                     "bad return values from evalBinOp " ++ show bop ++ ": " ++ show tpr
         ([TyInt _, TyInt _], [e1, e2]) -> do
             (result, overflow) <- evalBinOp bop (Just Signed) e1 e2
-            pos <- isPos e2
+            pos <- case bop of
+                -- A product overflows toward `MAX` when the operands have the same sign.
+                Mul -> R.App <$> (E.BoolEq <$> isPos e1 <*> isPos e2)
+                _ -> isPos e2
             case result of
                 MirExp (C.BVRepr w) result' -> do
                     satValue <- case saturateValueSigned w bop pos of
@@ -773,6 +838,14 @@ saturating_sub =
     , makeSaturatingArith "saturating_sub" Sub
     )
 
+-- There is no `saturating_mul` intrinsic, so this overrides a hook inside the `saturating_mul`
+-- method of each integer type.
+saturating_mul ::  (ExplodedDefId, CustomRHS)
+saturating_mul =
+    ( ["core","num","{{impl}}","saturating_mul","crucible_saturating_mul_hook"]
+    , makeSaturatingArith "saturating_mul" Mul
+    )
+
 
 -- | Common implementation for `unchecked_add` and related intrinsics.  These
 -- all perform the normal arithmetic operation, but overflow is undefined
@@ -957,6 +1030,19 @@ mem_swap = (["core","mem", "swap"],
         _ -> mirFail $ "bad arguments to mem_swap: " ++ show (opTys, ops)
     )
 
+-- mem::replace is implemented with mem::swap, but doing it directly avoids a
+-- temporary: read the old value, then store the new one.
+mem_replace ::  (ExplodedDefId, CustomRHS)
+mem_replace = (["core","mem", "replace"],
+    \ _substs -> Just $ CustomOp $ \ opTys ops -> case ops of
+        [MirExp (MirReferenceRepr ty1) e1, MirExp ty2 e2]
+          | Just Refl <- testEquality ty1 ty2 -> do
+            old <- readMirRef ty1 e1
+            writeMirRef e1 e2
+            return $ MirExp ty1 old
+        _ -> mirFail $ "bad arguments to mem_replace: " ++ show (opTys, ops)
+    )
+
 
 -- This is like normal mem::transmute, but requires source and target types to
 -- have identical Crucible `TypeRepr`s.
@@ -1344,13 +1430,17 @@ bv_lt = (["crucible", "bitvector", "{{impl}}", "lt"], \(Substs [_sz]) ->
 type BVMakeLiteral = forall ext f w.
     (1 <= w) => NatRepr w -> E.App ext f (C.BVType w)
 
+-- The width is erased from the substs, so it's taken from the return type.
 bv_literal :: Text -> BVMakeLiteral -> (ExplodedDefId, CustomRHS)
-bv_literal name op = (["crucible", "bitvector", "{{impl}}", name], \(Substs [sz]) ->
-    Just $ CustomOp $ \_optys _ops -> tyToReprM (CTyBv sz) >>= \(Some tpr) -> case tpr of
-        C.BVRepr w ->
-            return $ MirExp (C.BVRepr w) $ S.app $ op w
-        _ -> mirFail $
-            "BUG: invalid type param for bv_" ++ Text.unpack name ++ ": " ++ show sz)
+bv_literal name op = (["crucible", "bitvector", "{{impl}}", name], \_substs ->
+    Just $ CustomOpNamed $ \fnName _ops -> do
+        fn <- findFn fnName
+        let ty = fn ^. fsig . fsreturn_ty
+        tyToReprM ty >>= \(Some tpr) -> case tpr of
+            C.BVRepr w ->
+                return $ MirExp (C.BVRepr w) $ S.app $ op w
+            _ -> mirFail $
+                "BUG: invalid return type for bv_" ++ Text.unpack name ++ ": " ++ show ty)
 
 bv_leading_zeros :: (ExplodedDefId, CustomRHS)
 bv_leading_zeros =
@@ -1369,6 +1459,7 @@ allocate = (["crucible", "alloc", "allocate"], \substs -> case substs of
         [MirExp UsizeRepr len] -> do
             -- Create an uninitialized `MirVector_PartialVector` of length
             -- `len`, and return a pointer to its first element.
+            noteAllocation
             Some tpr <- tyToReprM t
             vec <- mirVector_uninit tpr len
             ref <- newMirRef (MirVectorRepr tpr)
@@ -1384,6 +1475,7 @@ allocate_zeroed :: (ExplodedDefId, CustomRHS)
 allocate_zeroed = (["crucible", "alloc", "allocate_zeroed"], \substs -> case substs of
     Substs [t] -> Just $ CustomOp $ \_ ops -> case ops of
         [MirExp UsizeRepr len] -> do
+            noteAllocation
             Some tpr <- tyToReprM t
             zero <- mkZero tpr
             let lenNat = R.App $ usizeToNat len
@@ -1419,6 +1511,14 @@ reallocate = (["crucible", "alloc", "reallocate"], \substs -> case substs of
         _ -> mirFail $ "BUG: invalid arguments to reallocate: " ++ show ops
     _ -> Nothing)
 
+-- fn allocation_count() -> usize
+allocation_count :: (ExplodedDefId, CustomRHS)
+allocation_count = (["crucible", "stats", "allocation_count"], \_ -> Just $ CustomOp $ \_ _ -> do
+    mac <- use $ cs . allocCount
+    case mac of
+        Just ac -> MirExp UsizeRepr <$> G.readGlobal ac
+        Nothing -> return $ MirExp UsizeRepr $ R.App $ usizeLit 0)
+
 -- No `deallocate` for now - we'd need some extra MirRef ops to implement that
 -- (since we need to get from the first-element pointer to the underlying
 -- RefCell that we want to drop).
@@ -1454,18 +1554,32 @@ atomic_load_impl = \_substs -> Just $ CustomOp $ \_ ops -> case ops of
     _ -> mirFail $ "BUG: invalid arguments to atomic_load: " ++ show ops
 
 atomic_cxchg_impl :: CustomRHS
-atomic_cxchg_impl = \_substs -> Just $ CustomOp $ \opTys ops -> case (opTys, ops) of
+atomic_cxchg_impl = atomic_cxchg_common "cxchg" (return S.false)
+
+-- `compare_exchange_weak` is allowed to fail even when the comparison
+-- succeeds.  We model this using a fresh symbolic boolean, so that both the
+-- spurious failure and the normal outcome are explored.
+atomic_cxchgweak_impl :: CustomRHS
+atomic_cxchgweak_impl = atomic_cxchg_common "cxchgweak" $
+    R.AtomExpr <$> G.mkFresh C.BaseBoolRepr Nothing
+
+atomic_cxchg_common ::
+    String ->
+    (forall h s ret. MirGenerator h s ret (R.Expr MIR s C.BoolType)) ->
+    CustomRHS
+atomic_cxchg_common name mkSpurious = \_substs -> Just $ CustomOp $ \opTys ops -> case (opTys, ops) of
     ([_, ty, _], [MirExp (MirReferenceRepr tpr) ref, MirExp tpr' expect, MirExp tpr'' val])
       | Just Refl <- testEquality tpr tpr'
       , Just Refl <- testEquality tpr tpr''
       , C.BVRepr w <- tpr -> do
         old <- readMirRef tpr ref
-        let eq = R.App $ E.BVEq w old expect
+        spurious <- mkSpurious
+        let eq = R.App $ E.And (R.App $ E.BVEq w old expect) (R.App $ E.Not spurious)
         let new = R.App $ E.BVIte eq w val old
         writeMirRef ref new
         buildTupleMaybeM [ty, TyBool] $
             [Just $ MirExp tpr old, Just $ MirExp C.BoolRepr eq]
-    _ -> mirFail $ "BUG: invalid arguments to atomic_cxchg: " ++ show ops
+    _ -> mirFail $ "BUG: invalid arguments to atomic_" ++ name ++ ": " ++ show ops
 
 atomic_fence_impl :: CustomRHS
 atomic_fence_impl = \_substs -> Just $ CustomOp $ \_ ops -> case ops of
@@ -1509,7 +1623,7 @@ atomic_funcs =
     makeAtomicIntrinsics "store" ["rel", "relaxed"] atomic_store_impl ++
     makeAtomicIntrinsics "load" ["acq", "relaxed"] atomic_load_impl ++
     makeAtomicIntrinsics "cxchg" compareExchangeVariants atomic_cxchg_impl ++
-    makeAtomicIntrinsics "cxchgweak" compareExchangeVariants atomic_cxchg_impl ++
+    makeAtomicIntrinsics "cxchgweak" compareExchangeVariants atomic_cxchgweak_impl ++
     makeAtomicIntrinsics "fence" fenceVariants atomic_fence_impl ++
     makeAtomicIntrinsics "singlethreadfence" fenceVariants atomic_fence_impl ++
     concat [
@@ -1532,6 +1646,65 @@ atomic_funcs =
     fenceVariants = ["acq", "rel", "acqrel"]
 
 
+--------------------------------------------------------------------------------------------------------------------------
+-- ** Custom: floating-point intrinsics
+--
+-- These are the intrinsics behind `f64::sqrt`, `f64::abs`, and so on.  They
+-- map directly onto the solver's floating-point operations, rather than
+-- running the `libm` implementations.
+
+float_funcs :: [(ExplodedDefId, CustomRHS)]
+float_funcs = concat
+    [ float_unop "sqrt" $ \fi x -> return $ S.app $ E.FloatSqrt fi W4.RNE x
+    , float_unop "fabs" $ \fi x -> return $ S.app $ E.FloatAbs fi x
+    , float_unop "floor" $ float_round W4.RTN
+    , float_unop "ceil" $ float_round W4.RTP
+    , float_unop "trunc" $ float_round W4.RTZ
+    , float_unop "round" $ float_round W4.RNA
+    , float_binop "copysign" $ \fi x y -> do
+        -- The solver's floats don't give NaNs a sign, so a NaN `y` counts as
+        -- positive, even `-NaN`, where Rust would use its sign bit.  As with
+        -- `FloatAbs`, a NaN result's sign is not modeled either.
+        let absX = S.app $ E.FloatAbs fi x
+        return $ S.app $ E.FloatIte fi (S.app $ E.FloatIsNegative y)
+            (S.app $ E.FloatNeg fi absX) absX
+    ]
+  where
+    float_round :: W4.RoundingMode -> FloatUnOp
+    float_round rm fi x = G.extensionStmt $ MirFloatRound fi rm x
+
+type FloatUnOp = forall h s ret fi.
+    C.FloatInfoRepr fi ->
+    R.Expr MIR s (C.FloatType fi) ->
+    MirGenerator h s ret (R.Expr MIR s (C.FloatType fi))
+
+type FloatBinOp = forall h s ret fi.
+    C.FloatInfoRepr fi ->
+    R.Expr MIR s (C.FloatType fi) ->
+    R.Expr MIR s (C.FloatType fi) ->
+    MirGenerator h s ret (R.Expr MIR s (C.FloatType fi))
+
+-- | Define the `f32` and `f64` versions of a unary float intrinsic, such as
+-- `sqrtf32` and `sqrtf64` for `"sqrt"`.
+float_unop :: Text -> FloatUnOp -> [(ExplodedDefId, CustomRHS)]
+float_unop name op =
+    [ (["core", "intrinsics", "", name <> suffix], \_substs ->
+        Just $ CustomOp $ \_optys ops -> case ops of
+            [MirExp (C.FloatRepr fi) x] -> MirExp (C.FloatRepr fi) <$> op fi x
+            _ -> mirFail $ "BUG: invalid arguments to " ++ Text.unpack (name <> suffix) ++ ": " ++ show ops)
+    | suffix <- ["f32", "f64"] ]
+
+float_binop :: Text -> FloatBinOp -> [(ExplodedDefId, CustomRHS)]
+float_binop name op =
+    [ (["core", "intrinsics", "", name <> suffix], \_substs ->
+        Just $ CustomOp $ \_optys ops -> case ops of
+            [MirExp (C.FloatRepr fi) x, MirExp (C.FloatRepr fi') y]
+              | Just Refl <- testEquality fi fi' ->
+                MirExp (C.FloatRepr fi) <$> op fi x y
+            _ -> mirFail $ "BUG: invalid arguments to " ++ Text.unpack (name <> suffix) ++ ": " ++ show ops)
+    | suffix <- ["f32", "f64"] ]
+
+
 --------------------------------------------------------------------------------------------------------------------------
 -- MaybeUninit
 
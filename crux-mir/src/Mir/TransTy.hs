@@ -34,6 +34,7 @@ import qualified Data.Vector as V
 import qualified Data.Text as Text
 
 import GHC.Stack
+import Numeric.Natural (Natural)
 
 -- parameterized-utils
 import qualified Data.Parameterized.Context as Ctx
@@ -97,6 +98,11 @@ baseSizeToNatCont M.B64  k = k (knownNat :: NatRepr 64)
 baseSizeToNatCont M.B128 k = k (knownNat :: NatRepr 128)
 baseSizeToNatCont M.USize k = k (knownNat :: NatRepr SizeBits)
 
+-- | convert a floatKind to the corresponding crucible float format
+floatKindToFloatInfoCont :: M.FloatKind -> (forall fi. C.FloatInfoRepr fi -> a) -> a
+floatKindToFloatInfoCont M.F32 k = k C.SingleFloatRepr
+floatKindToFloatInfoCont M.F64 k = k C.DoubleFloatRepr
+
 
 -- Custom type aliases
 pattern CTyInt512 <- M.TyAdt _ $(M.normDefIdPat "int512::Int512") (M.Substs [])
@@ -114,17 +120,9 @@ pattern CTyVector t <- M.TyAdt _ $(M.normDefIdPat "crucible::vector::Vector") (M
 pattern CTyArray t <- M.TyAdt _ $(M.normDefIdPat "crucible::array::Array") (M.Substs [t])
   where CTyArray t = M.TyAdt (M.textId "type::adt") (M.textId "crucible::array::Array") (M.Substs [t])
 
-pattern CTyBvSize128 <- M.TyAdt _ $(M.normDefIdPat "crucible::bitvector::_128") (M.Substs [])
-  where CTyBvSize128 = M.TyAdt (M.textId "type::adt") (M.textId "crucible::bitvector::_128") (M.Substs [])
-pattern CTyBvSize256 <- M.TyAdt _ $(M.normDefIdPat "crucible::bitvector::_256") (M.Substs [])
-  where CTyBvSize256 = M.TyAdt (M.textId "type::adt") (M.textId "crucible::bitvector::_256") (M.Substs [])
-pattern CTyBvSize512 <- M.TyAdt _ $(M.normDefIdPat "crucible::bitvector::_512") (M.Substs [])
-  where CTyBvSize512 = M.TyAdt (M.textId "type::adt") (M.textId "crucible::bitvector::_512") (M.Substs [])
+-- | `crucible::bitvector::Bv<N>`.  Its only type argument is the erased
+-- constant `N`; use `bvWidth` to get the width.
 pattern CTyBv t <- M.TyAdt _ $(M.normDefIdPat "crucible::bitvector::Bv") (M.Substs [t])
-  where CTyBv t = M.TyAdt (M.textId "type::adt") (M.textId "crucible::bitvector::Bv") (M.Substs [t])
-pattern CTyBv128 = CTyBv CTyBvSize128
-pattern CTyBv256 = CTyBv CTyBvSize256
-pattern CTyBv512 = CTyBv CTyBvSize512
 
 pattern CTyAny <- M.TyAdt _ $(M.normDefIdPat "core::crucible::any::Any") (M.Substs [])
   where CTyAny = M.TyAdt (M.textId "type::adt") (M.textId "core::crucible::any::Any") (M.Substs [])
@@ -149,12 +147,26 @@ optionDiscrSome :: Int
 optionDiscrSome = 1
 
 
+-- | The width `N` of a `crucible::bitvector::Bv<N>` type.  Constant arguments
+-- are erased from `Substs`, so this reads `N` from the type of the `Bv`'s
+-- `PhantomData<[(); N]>` field in the monomorphized ADT.
+bvWidth :: M.Collection -> M.Ty -> Maybe Natural
+bvWidth col (M.TyAdt name _ _) = do
+    adt <- col ^. M.adts . at name
+    [v] <- return $ adt ^. M.adtvariants
+    Maybe.listToMaybe
+        [ fromIntegral n
+        | M.Field _ (M.TyAdt _ _ (M.Substs [M.TyArray _ n])) <- v ^. M.vfields ]
+bvWidth _ _ = Nothing
+
 tyToRepr :: TransTyConstraint => M.Collection -> M.Ty -> Some C.TypeRepr
 tyToRepr col t0 = case t0 of
   CTyInt512 -> Some $ C.BVRepr (knownNat :: NatRepr 512)
-  CTyBv128 -> Some $ C.BVRepr (knownNat :: NatRepr 128)
-  CTyBv256 -> Some $ C.BVRepr (knownNat :: NatRepr 256)
-  CTyBv512 -> Some $ C.BVRepr (knownNat :: NatRepr 512)
+  CTyBv _
+    | Just n <- bvWidth col t0
+    , Some w <- mkNatRepr n
+    , Just LeqProof <- isPosNat w -> Some $ C.BVRepr w
+    | otherwise -> error $ "unsupported: bitvector type " ++ show t0 ++ " (the width must be at least 1)"
   CTyVector t -> tyToReprCont col t $ \repr -> Some (C.VectorRepr repr)
   CTyArray t
     | Some tpr <- tyToRepr col t
@@ -215,7 +227,7 @@ tyToRepr col t0 = case t0 of
     | otherwise -> Some C.AnyRepr
   M.TyDowncast _adt _i   -> Some C.AnyRepr
 
-  M.TyFloat _ -> Some C.RealValRepr
+  M.TyFloat fk -> floatKindToFloatInfoCont fk $ \fi -> Some (C.FloatRepr fi)
 
   -- non polymorphic function types go to FunctionHandleRepr
   M.TyFnPtr sig@(M.FnSig args ret _abi _spread) ->
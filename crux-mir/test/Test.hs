@@ -5,6 +5,7 @@
 
 import qualified Data.ByteString as BS
 import qualified Data.ByteString.UTF8 as BS8
+import           Control.Monad (forM_)
 import           Data.Char (isSpace)
 import           Data.List (dropWhileEnd, isPrefixOf)
 import           Data.Maybe (catMaybes)
@@ -52,6 +53,25 @@ expectedFail fn =
          else Nothing
   where failMarker = "// FAIL: "
 
+-- | Extra crux-mir flags for a symbolic test, given by a comment on the first
+-- line, like `// FLAGS: --no-overflow-checks`.
+testFlags :: FilePath -> IO [String]
+testFlags fn =
+  withFile fn ReadMode $ \h ->
+  do firstLine <- hGetLine h
+     return $
+       if flagsMarker `isPrefixOf` firstLine
+         then words (drop (length flagsMarker) firstLine)
+         else []
+  where flagsMarker = "// FLAGS: "
+
+applyTestFlag :: String -> Mir.MIROptions -> Mir.MIROptions
+applyTestFlag "--concurrency" opts = opts { Mir.concurrency = True }
+applyTestFlag "--no-overflow-checks" opts = opts { Mir.noOverflowChecks = True }
+applyTestFlag "--distinct-failures" opts = opts { Mir.distinctFailures = True }
+applyTestFlag "--check-assumptions" opts = opts { Mir.checkAssumptions = True }
+applyTestFlag flag _ = error $ "unsupported flag in test: " ++ flag
+
 
 -- TODO: remove this - copy-pasted from Crux/Options.hs for compatibility with
 -- old mainline crucible
@@ -66,8 +86,8 @@ defaultCruxOptions = case res of
 data RunCruxMode = RcmConcrete | RcmSymbolic | RcmCoverage
   deriving (Show, Eq)
 
-runCrux :: FilePath -> Handle -> RunCruxMode -> IO ()
-runCrux rustFile outHandle mode =
+runCrux :: FilePath -> Handle -> RunCruxMode -> [String] -> IO ()
+runCrux rustFile outHandle mode flags =
   Mir.withMirLogging $
   do
     -- goalTimeout is bumped from 60 to 180 because scalar.rs symbolic
@@ -92,7 +112,9 @@ runCrux rustFile outHandle mode =
                                             RcmCoverage -> getOutputDir rustFile
                                             _ -> "",
                                         Crux.branchCoverage = (mode == RcmCoverage) } ,
-                   Mir.defaultMirOptions { Mir.printResultOnly = (mode == RcmConcrete) })
+                   foldr applyTestFlag
+                     (Mir.defaultMirOptions { Mir.printResultOnly = (mode == RcmConcrete) })
+                     flags)
     let ?outputConfig = Crux.mkOutputConfig (outHandle, False) (outHandle, False) Mir.mirLoggingToSayWhat $
                         Just (Crux.outputOptions (fst options))
     _exitCode <- Mir.runTests options
@@ -115,7 +137,7 @@ cruxOracleTest dir name step = do
   let rustFile = dir </> name <.> "rs"
 
   cruxOut <- withSystemTempFile name $ \tempName h -> do
-    runCrux rustFile h RcmConcrete
+    runCrux rustFile h RcmConcrete []
     hClose h
     h' <- openFile tempName ReadMode
     out <- hGetContents h'
@@ -132,8 +154,9 @@ symbTest dir =
      return $
        testGroup "Output testing"
          [ doGoldenTest (takeBaseName rustFile) goodFile outFile $
-           withFile outFile WriteMode $ \h ->
-           runCrux rustFile h RcmSymbolic
+           do flags <- testFlags rustFile
+              withFile outFile WriteMode $ \h ->
+                runCrux rustFile h RcmSymbolic flags
          | rustFile <- rustFiles
          -- Skip hidden files, such as editor swap files
          , not $ "." `isPrefixOf` takeFileName rustFile
@@ -156,7 +179,7 @@ coverageTests dir = do
   where
     doTest rustFile outFile = do
         let logFile = replaceExtension rustFile ".crux.log"
-        withFile logFile WriteMode $ \h -> runCrux rustFile h RcmCoverage
+        withFile logFile WriteMode $ \h -> runCrux rustFile h RcmCoverage []
         let reportDir = getOutputDir rustFile </> takeBaseName rustFile
         reportFiles <- findByExtension [".js"] reportDir
         out <- Proc.readProcess "cargo"
@@ -166,6 +189,28 @@ coverageTests dir = do
 
 
 
+-- | Check that a concurrent test that fails in every execution logs the
+-- interleaving of the first failing one: `threads` threads besides the main
+-- one start, and the main thread joins them.
+interleavingTest :: FilePath -> Int -> TestTree
+interleavingTest rustFile threads = testCaseSteps (takeBaseName rustFile) $ \step -> do
+  step "Running crux-mir with --concurrency"
+  out <- withSystemTempFile (takeBaseName rustFile) $ \tempName h -> do
+    runCrux rustFile h RcmSymbolic ["--concurrency"]
+    hClose h
+    h' <- openFile tempName ReadMode
+    out <- hGetContents h'
+    length out `seq` hClose h'
+    return out
+  step ("Crux output: " ++ out)
+  let outLines = lines out
+      hasStep s = any (("[Cruces]   thread " ++ s) `isPrefixOf`) outLines
+  assertBool "no interleaving logged" $
+    "[Cruces] Interleaving of the failing execution:" `elem` outLines
+  forM_ [1 .. threads] $ \t ->
+    assertBool ("thread " ++ show t ++ " doesn't start") $ hasStep (show t ++ ": start")
+  assertBool "the main thread doesn't join" $ hasStep "0: finish joining"
+
 doGoldenTest :: FilePath -> FilePath -> FilePath -> IO () -> TestTree
 doGoldenTest rustFile goodFile outFile act = goldenTest (takeBaseName rustFile)
     (BS.readFile goodFile)
@@ -187,6 +232,8 @@ suite = do
            [ testGroup "crux concrete" <$> sequence [ testDir cruxOracleTest "test/conc_eval/" ]
            , testGroup "crux symbolic" <$> sequence [ symbTest "test/symb_eval" ]
            , testGroup "crux coverage" <$> sequence [ coverageTests "test/coverage" ]
+           , return $ testGroup "crux concurrency"
+               [ interleavingTest "test/concurrency/atomic/atomic_fetch_wrong_sum.rs" 3 ]
            ]
   return $ testGroup "crux-mir" trees
 
@@ -0,0 +1,50 @@
+extern crate crucible;
+use crucible::*;
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+const N: usize = 2;
+
+// Each thread holds a clone until the main thread tells it to stop, so once every thread is
+// ready, the count is exactly `N + 1`, whatever the interleaving.  Once they have all been
+// joined, only the original is left.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let shared = Arc::new(5);
+    let (ready_tx, ready_rx) = mpsc::channel::<()>();
+    let mut stops = Vec::new();
+    let mut handles = Vec::new();
+    for _ in 0..N {
+        let a = Arc::clone(&shared);
+        let ready_tx = ready_tx.clone();
+        let (tx, rx) = mpsc::channel::<()>();
+        stops.push(tx);
+        handles.push(thread::spawn(move || {
+            // A temporary clone is counted too, and dropping it gives the count back.
+            let b = Arc::clone(&a);
+            crucible_assert!(Arc::strong_count(&b) >= 3);
+            drop(b);
+            crucible_assert!(*a == 5);
+            ready_tx.send(()).unwrap();
+            rx.recv().unwrap();
+        }));
+    }
+
+    crucible_assert!(Arc::strong_count(&shared) >= N + 1);
+    for _ in 0..N {
+        ready_rx.recv().unwrap();
+    }
+    let mut stopped = 0;
+    for tx in stops {
+        // Each thread still holds its clone until it receives this.
+        crucible_assert!(Arc::strong_count(&shared) == N + 1 - stopped);
+        tx.send(()).unwrap();
+        handles.remove(0).join().unwrap();
+        stopped += 1;
+    }
+    crucible_assert!(Arc::strong_count(&shared) == 1);
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,20 @@
+extern crate crucible;
+use crucible::*;
+use std::sync::Arc;
+use std::thread;
+
+// Without waiting for the thread, the count depends on whether it has dropped its clone yet.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_fail() {
+    let shared = Arc::new(5);
+    let a = Arc::clone(&shared);
+    let h = thread::spawn(move || {
+        crucible_assert!(*a == 5);
+    });
+    crucible_assert!(Arc::strong_count(&shared) == 2);
+    h.join().unwrap();
+}
+
+#[cfg(with_main)]
+fn main() {}
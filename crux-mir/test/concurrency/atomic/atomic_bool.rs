@@ -0,0 +1,49 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc,atomic};
+use std::sync::atomic::{AtomicBool, AtomicU16};
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let N       = 2;
+
+    let first       = Arc::new(AtomicBool::new(false));
+    let any         = Arc::new(AtomicBool::new(false));
+    let all         = Arc::new(AtomicBool::new(true));
+    let parity      = Arc::new(AtomicBool::new(false));
+    let wins        = Arc::new(AtomicU16::new(0));
+    let mut threads = vec![];
+
+    for x in 0..N {
+        let f = Arc::clone(&first);
+        let a = Arc::clone(&any);
+        let l = Arc::clone(&all);
+        let p = Arc::clone(&parity);
+        let w = Arc::clone(&wins);
+        let c = thread::spawn(move || {
+            if !f.swap(true, atomic::Ordering::SeqCst) {
+                w.fetch_add(1, atomic::Ordering::SeqCst);
+            }
+            a.fetch_or(x == 1, atomic::Ordering::SeqCst);
+            l.fetch_and(x == 1, atomic::Ordering::SeqCst);
+            p.fetch_xor(true, atomic::Ordering::SeqCst);
+        });
+        threads.push(c);
+    }
+
+    for t in threads {
+        t.join();
+    }
+
+    // Exactly one thread saw `first` still unset.
+    crucible_assert!(wins.load(atomic::Ordering::SeqCst) == 1);
+    crucible_assert!(any.load(atomic::Ordering::SeqCst));
+    crucible_assert!(!all.load(atomic::Ordering::SeqCst));
+    // Each thread flipped `parity` once.
+    crucible_assert!(!parity.load(atomic::Ordering::SeqCst));
+}
+
+#[cfg(with_main)]
+fn main() {}
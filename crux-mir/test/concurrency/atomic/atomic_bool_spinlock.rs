@@ -0,0 +1,56 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc,atomic};
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+
+struct SpinLock {
+    locked: AtomicBool,
+}
+
+impl SpinLock {
+    fn lock(&self) {
+        // A thread that finds the lock taken would spin until it's released.  Spinning has no
+        // effect, so such an execution is equivalent to one in which the thread tried the lock
+        // after it was released, and it's discarded instead of being explored forever.
+        if self.locked.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            crucible_assume_unreachable!();
+        }
+    }
+
+    fn unlock(&self) {
+        self.locked.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let N       = 2;
+
+    let lock        = Arc::new(SpinLock { locked: AtomicBool::new(false) });
+    let counter     = Arc::new(AtomicU16::new(0));
+    let mut threads = vec![];
+
+    for _ in 0..N {
+        let l = Arc::clone(&lock);
+        let c = Arc::clone(&counter);
+        let t = thread::spawn(move || {
+            l.lock();
+            // Not atomic on its own: the lock keeps another thread from running in between.
+            let v = c.load(Ordering::Relaxed);
+            c.store(v + 1, Ordering::Relaxed);
+            l.unlock();
+        });
+        threads.push(t);
+    }
+
+    for t in threads {
+        t.join();
+    }
+
+    crucible_assert!(counter.load(atomic::Ordering::SeqCst) == N);
+}
+
+#[cfg(with_main)]
+fn main() {}
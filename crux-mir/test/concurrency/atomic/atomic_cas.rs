@@ -0,0 +1,40 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc,atomic};
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let N       = 2;
+
+    let cell        = Arc::new(atomic::AtomicU16::new(0));
+    let wins        = Arc::new(atomic::AtomicU16::new(0));
+    let mut threads = vec![];
+
+    for _ in 0..N {
+        let d = Arc::clone(&cell);
+        let w = Arc::clone(&wins);
+        let c = thread::spawn(move || {
+            let r = d.compare_exchange(0, 1, atomic::Ordering::SeqCst, atomic::Ordering::SeqCst);
+            match r {
+                Ok(old) => {
+                    crucible_assert!(old == 0);
+                    w.fetch_add(1, atomic::Ordering::SeqCst);
+                }
+                Err(old) => crucible_assert!(old == 1),
+            }
+        });
+        threads.push(c);
+    }
+
+    for t in threads {
+        t.join();
+    }
+
+    crucible_assert!(cell.load(atomic::Ordering::SeqCst) == 1);
+    crucible_assert!(wins.load(atomic::Ordering::SeqCst) == 1);
+}
+
+#[cfg(with_main)]
+fn main() {}
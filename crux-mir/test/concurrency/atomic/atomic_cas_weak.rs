@@ -0,0 +1,25 @@
+extern crate crucible;
+use crucible::*;
+use std::sync::atomic;
+
+// `compare_exchange_weak` may fail spuriously, so both outcomes must be
+// reachable even though the comparison always succeeds.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let cell = atomic::AtomicU16::new(0);
+
+    match cell.compare_exchange_weak(0, 1, atomic::Ordering::AcqRel, atomic::Ordering::Acquire) {
+        Ok(old) => {
+            crucible_assert!(old == 0);
+            crucible_assert!(cell.load(atomic::Ordering::SeqCst) == 1);
+        }
+        Err(old) => {
+            crucible_assert!(old == 0);
+            crucible_assert!(cell.load(atomic::Ordering::SeqCst) == 0);
+        }
+    }
+}
+
+#[cfg(with_main)]
+fn main() {}
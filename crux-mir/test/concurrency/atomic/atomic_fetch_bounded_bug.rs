@@ -0,0 +1,36 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc,atomic};
+
+// The increment is a separate load and store, so an update is lost if another
+// thread stores in between.  That takes only one preemption, so it's still
+// found with a small bound.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_fail() {
+    max_preemptions(1);
+    let N       = 5;
+
+    let cell        = Arc::new(atomic::AtomicU16::new(0));
+    let mut threads = vec![];
+
+    for x in 0..N {
+        let d = Arc::clone(&cell);
+        let c = thread::spawn(move || {
+            let v = d.load(atomic::Ordering::SeqCst);
+            d.store(v + x, atomic::Ordering::SeqCst);
+        });
+        threads.push(c);
+    }
+
+    for t in threads {
+        t.join();
+    }
+
+    let val = cell.load(atomic::Ordering::SeqCst);
+    crucible_assert!(val == N*(N-1)/2);
+}
+
+#[cfg(with_main)]
+fn main() {}
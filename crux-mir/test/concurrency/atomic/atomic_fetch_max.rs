@@ -0,0 +1,32 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc,atomic};
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let N       = 3;
+
+    let cell        = Arc::new(atomic::AtomicU32::new(0));
+    let mut threads = vec![];
+
+    for x in 0..N {
+        let d = Arc::clone(&cell);
+        let c = thread::spawn(move || {
+            let old = d.fetch_max(x, atomic::Ordering::SeqCst);
+            crucible_assert!(old < N);
+        });
+        threads.push(c);
+    }
+
+    for t in threads {
+        t.join();
+    }
+
+    let val = cell.load(atomic::Ordering::SeqCst);
+    crucible_assert!(val == N-1);
+}
+
+#[cfg(with_main)]
+fn main() {}
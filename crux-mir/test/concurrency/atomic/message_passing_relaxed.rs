@@ -0,0 +1,28 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc,atomic};
+use std::sync::atomic::Ordering;
+
+// The flag is stored with `Relaxed`, so the reader may see it set before the
+// data is.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_fail() {
+    let data  = Arc::new(atomic::AtomicU32::new(0));
+    let ready = Arc::new(atomic::AtomicBool::new(false));
+
+    let (d, r) = (Arc::clone(&data), Arc::clone(&ready));
+    let writer = thread::spawn(move || {
+        d.store(1, Ordering::Relaxed);
+        r.store(true, Ordering::Relaxed);
+    });
+
+    if ready.load(Ordering::Acquire) {
+        crucible_assert!(data.load(Ordering::Relaxed) == 1);
+    }
+    writer.join();
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,30 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc,atomic};
+use std::sync::atomic::Ordering;
+
+// Storing the flag with `Release` makes the data visible first.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let data  = Arc::new(atomic::AtomicU32::new(0));
+    let ready = Arc::new(atomic::AtomicBool::new(false));
+
+    let (d, r) = (Arc::clone(&data), Arc::clone(&ready));
+    let writer = thread::spawn(move || {
+        d.store(1, Ordering::Relaxed);
+        r.store(true, Ordering::Release);
+    });
+
+    if ready.load(Ordering::Acquire) {
+        crucible_assert!(data.load(Ordering::Relaxed) == 1);
+    }
+    writer.join();
+    // Once the writer has been joined, both of its stores are visible.
+    crucible_assert!(data.load(Ordering::Relaxed) == 1);
+    crucible_assert!(ready.load(Ordering::Relaxed));
+}
+
+#[cfg(with_main)]
+fn main() {}
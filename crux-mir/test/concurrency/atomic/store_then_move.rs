@@ -0,0 +1,34 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+fn stored(x: u32) -> AtomicU32 {
+    let a = AtomicU32::new(0);
+    a.store(x, Ordering::Relaxed);
+    a
+}
+
+// A `Relaxed` store is buffered, but the storing thread must still see it
+// after the atomic is moved, whether it's read with `load` or `into_inner`.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test() {
+    let t = thread::spawn(|| {
+        let a = stored(1);
+        crucible_assert!(a.load(Ordering::Relaxed) == 1);
+
+        let b = stored(2);
+        let moved = b;
+        crucible_assert!(moved.into_inner() == 2);
+
+        let flag = AtomicBool::new(false);
+        flag.store(true, Ordering::Release);
+        let flags = [flag];
+        crucible_assert!(flags[0].load(Ordering::Relaxed));
+    });
+    t.join().unwrap();
+}
+
+#[cfg(with_main)]
+fn main() {}
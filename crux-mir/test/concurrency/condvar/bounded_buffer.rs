@@ -0,0 +1,59 @@
+extern crate crucible;
+use crucible::*;
+use std::collections::VecDeque;
+use std::thread;
+use std::sync::{Arc, Condvar, Mutex};
+
+const CAP: usize = 1;
+
+struct Buffer {
+    items: Mutex<VecDeque<u32>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+// A producer and a consumer share a one-slot buffer.  Each waits on a condition
+// variable until the other makes room or adds an item.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let buf = Arc::new(Buffer {
+        items: Mutex::new(VecDeque::new()),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+    });
+
+    let b = Arc::clone(&buf);
+    let producer = thread::spawn(move || {
+        for x in 1..=3 {
+            let mut items = b.items.lock().unwrap();
+            while items.len() == CAP {
+                items = b.not_full.wait(items).unwrap();
+            }
+            items.push_back(x);
+            crucible_assert!(items.len() <= CAP);
+            b.not_empty.notify_one();
+        }
+    });
+
+    let b = Arc::clone(&buf);
+    let consumer = thread::spawn(move || {
+        let mut sum = 0;
+        for _ in 0..3 {
+            let mut items = b.items.lock().unwrap();
+            while items.is_empty() {
+                items = b.not_empty.wait(items).unwrap();
+            }
+            sum += items.pop_front().unwrap();
+            b.not_full.notify_one();
+        }
+        sum
+    });
+
+    producer.join().unwrap();
+    let sum = consumer.join().unwrap();
+    crucible_assert!(sum == 6);
+}
+
+#[cfg(with_main)]
+fn main() {}
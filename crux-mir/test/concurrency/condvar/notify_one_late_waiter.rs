@@ -0,0 +1,43 @@
+extern crate crucible;
+use std::thread;
+use std::sync::{Arc, Condvar, Mutex};
+
+// Only the first thread can be waiting when `notify_one` runs.  The second
+// thread starts waiting afterward, so it can't take that wakeup and leave the
+// first thread waiting forever.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    // The flags say whether each thread may stop waiting.
+    let state = Arc::new((Mutex::new((false, false)), Condvar::new()));
+
+    let s = Arc::clone(&state);
+    let first = thread::spawn(move || {
+        let (lock, cvar) = &*s;
+        let mut flags = lock.lock().unwrap();
+        while !flags.0 {
+            flags = cvar.wait(flags).unwrap();
+        }
+    });
+
+    let (lock, cvar) = &*state;
+    lock.lock().unwrap().0 = true;
+    cvar.notify_one();
+
+    let s = Arc::clone(&state);
+    let second = thread::spawn(move || {
+        let (lock, cvar) = &*s;
+        let mut flags = lock.lock().unwrap();
+        while !flags.1 {
+            flags = cvar.wait(flags).unwrap();
+        }
+    });
+
+    first.join().unwrap();
+    lock.lock().unwrap().1 = true;
+    cvar.notify_all();
+    second.join().unwrap();
+}
+
+#[cfg(with_main)]
+fn main() {}
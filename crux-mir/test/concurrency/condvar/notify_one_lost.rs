@@ -0,0 +1,35 @@
+extern crate crucible;
+use std::thread;
+use std::sync::{Arc, Condvar, Mutex};
+
+// Both threads may be waiting when `notify_one` runs, in which case only one
+// of them wakes up and the other waits forever.  This should be reported as a
+// deadlock; `notify_all` would be correct here.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_fail() {
+    let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    let mut children = vec![];
+
+    for _ in 0..2 {
+        let p = Arc::clone(&pair);
+        children.push(thread::spawn(move || {
+            let (lock, cvar) = &*p;
+            let mut ready = lock.lock().unwrap();
+            while !*ready {
+                ready = cvar.wait(ready).unwrap();
+            }
+        }));
+    }
+
+    let (lock, cvar) = &*pair;
+    *lock.lock().unwrap() = true;
+    cvar.notify_one();
+
+    for c in children {
+        c.join().unwrap();
+    }
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,54 @@
+extern crate crucible;
+use std::thread;
+use std::sync::{Arc, Condvar, Mutex};
+
+// Both threads are waiting when `notify_one` runs twice, but only the second
+// may stop waiting.  If the first takes a wakeup and waits again, it started
+// waiting after both signals, so it can't take the other one too and leave the
+// second thread waiting forever.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    // The number of threads waiting, and whether each thread may stop waiting.
+    let state = Arc::new((Mutex::new((0, false, false)), Condvar::new(), Condvar::new()));
+
+    let s = Arc::clone(&state);
+    let first = thread::spawn(move || {
+        let (lock, cvar, ready) = &*s;
+        let mut st = lock.lock().unwrap();
+        st.0 += 1;
+        ready.notify_one();
+        while !st.1 {
+            st = cvar.wait(st).unwrap();
+        }
+    });
+
+    let s = Arc::clone(&state);
+    let second = thread::spawn(move || {
+        let (lock, cvar, ready) = &*s;
+        let mut st = lock.lock().unwrap();
+        st.0 += 1;
+        ready.notify_one();
+        while !st.2 {
+            st = cvar.wait(st).unwrap();
+        }
+    });
+
+    let (lock, cvar, ready) = &*state;
+    let mut st = lock.lock().unwrap();
+    while st.0 < 2 {
+        st = ready.wait(st).unwrap();
+    }
+    st.2 = true;
+    cvar.notify_one();
+    cvar.notify_one();
+    drop(st);
+
+    second.join().unwrap();
+    lock.lock().unwrap().1 = true;
+    cvar.notify_all();
+    first.join().unwrap();
+}
+
+#[cfg(with_main)]
+fn main() {}
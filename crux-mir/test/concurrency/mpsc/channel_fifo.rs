@@ -0,0 +1,38 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::mpsc;
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let (tx, rx) = mpsc::channel();
+    let mut children = vec![];
+
+    for id in 0..2 {
+        let tx = tx.clone();
+        children.push(thread::spawn(move || {
+            tx.send((id, 0)).unwrap();
+            tx.send((id, 1)).unwrap();
+        }));
+    }
+    drop(tx);
+
+    // Values from different senders may interleave, but each sender's values
+    // arrive in the order they were sent.
+    let mut next = [0, 0];
+    let mut count = 0;
+    for (id, seq) in rx.iter() {
+        crucible_assert!(seq == next[id]);
+        next[id] += 1;
+        count += 1;
+    }
+    crucible_assert!(count == 4);
+
+    for c in children {
+        c.join();
+    }
+}
+
+#[cfg(with_main)]
+fn main() {}
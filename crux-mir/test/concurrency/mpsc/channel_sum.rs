@@ -0,0 +1,33 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::mpsc;
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let (tx, rx) = mpsc::channel();
+
+    let producer = thread::spawn(move || {
+        for i in 0..3 {
+            tx.send(i).unwrap();
+        }
+        // `tx` is dropped here, which disconnects the channel.
+    });
+
+    let mut sum = 0;
+    let mut expected = 0;
+    // `recv` blocks until a value arrives, and fails once the sender is gone.
+    while let Ok(x) = rx.recv() {
+        crucible_assert!(x == expected);
+        expected += 1;
+        sum += x;
+    }
+    crucible_assert!(sum == 3);
+    crucible_assert!(rx.try_recv() == Err(mpsc::TryRecvError::Disconnected));
+
+    producer.join();
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,35 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc,Mutex};
+
+// The two threads take the locks in opposite orders, so some interleavings
+// deadlock.  This should be reported as a deadlock, not hang.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_fail() {
+    let a = Arc::new(Mutex::new(0));
+    let b = Arc::new(Mutex::new(0));
+
+    let (a1, b1) = (Arc::clone(&a), Arc::clone(&b));
+    let h1 = thread::spawn(move || {
+        let ga = a1.lock().unwrap();
+        let gb = b1.lock().unwrap();
+        drop(gb);
+        drop(ga);
+    });
+
+    let (a2, b2) = (Arc::clone(&a), Arc::clone(&b));
+    let h2 = thread::spawn(move || {
+        let gb = b2.lock().unwrap();
+        let ga = a2.lock().unwrap();
+        drop(ga);
+        drop(gb);
+    });
+
+    h1.join();
+    h2.join();
+}
+
+#[cfg(with_main)]
+fn main() {}
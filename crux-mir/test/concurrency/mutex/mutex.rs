@@ -16,8 +16,7 @@ fn crux_test_nofail() {
             let mut p = d.lock().unwrap();
 
             *p += x;
-            // Until crux-mir supports drop()
-            d.crucible_TEMP_unlock();
+            drop(p);
 
             ()
         });
@@ -33,8 +32,7 @@ fn crux_test_nofail() {
 
     crucible_assert!(*p == sum); // Correct
 
-    // Until crux-mir supports drop()
-    data.crucible_TEMP_unlock();
+    drop(p);
 }
 
 #[cfg_attr(crux, crux_test)]
@@ -52,7 +50,7 @@ fn crux_test_fail() {
 
             if b { *p += x; }
 
-            d.crucible_TEMP_unlock();
+            drop(p);
 
             ()
         });
@@ -65,7 +63,7 @@ fn crux_test_fail() {
     let p = data.lock().unwrap();
     let sum = N*(N-1)/2;
     crucible_assert!(*p == sum);
-    data.crucible_TEMP_unlock();
+    drop(p);
 }
 
 
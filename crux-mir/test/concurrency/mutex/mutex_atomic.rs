@@ -21,8 +21,7 @@ fn crux_test_nofail() {
             let p = d.lock().unwrap();
             c.fetch_add(1, SeqCst);
             c.fetch_add(1, SeqCst);
-            // Until crux-mir supports drop()
-            d.crucible_TEMP_unlock();
+            drop(p);
 
             ()
         });
@@ -34,6 +33,5 @@ fn crux_test_nofail() {
 
     crucible_assert!(v % 2 == 0); // Correct
 
-    // Until crux-mir supports drop()
-    data.crucible_TEMP_unlock();
+    drop(p);
 }
@@ -0,0 +1,32 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc,Mutex};
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let data    = Arc::new(Mutex::new(Vec::new()));
+    let N       = 3;
+    let mut children = vec![];
+
+    for x in 0..N {
+        let d = Arc::clone(&data);
+        let a = thread::spawn(move || {
+            // The guard is a temporary, so the lock is released at the end of
+            // this statement.
+            d.lock().unwrap().push(x);
+        });
+        children.push(a);
+    }
+
+    for c in children {
+        c.join();
+    }
+
+    let v = data.lock().unwrap();
+    crucible_assert!(v.len() == N);
+}
+
+#[cfg(with_main)]
+fn main() {}
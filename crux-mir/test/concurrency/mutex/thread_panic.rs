@@ -0,0 +1,28 @@
+extern crate crucible;
+use crucible::*;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    // A panic in a spawned thread is returned from `join` as an `Err`.  Dropping
+    // the guard while unwinding is a scheduling point, so the main thread may run
+    // in the middle of the unwinding, which mustn't affect it.
+    let m = Arc::new(Mutex::new(0));
+    let m2 = Arc::clone(&m);
+    let h = thread::spawn(move || {
+        let mut g = m2.lock().unwrap_or_else(|e| e.into_inner());
+        *g += 1;
+        panic!("child panicked");
+    });
+    {
+        let mut g = m.lock().unwrap_or_else(|e| e.into_inner());
+        *g += 1;
+    }
+    crucible_assert!(h.join().is_err());
+    crucible_assert!(*m.lock().unwrap_or_else(|e| e.into_inner()) == 2);
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,35 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    crucible_assert!(thread::spawn(|| 42).join().unwrap() == 42);
+
+    // Each thread returns the sum of its own chunk.  The values are `u64`s, and
+    // one chunk depends on a symbolic value.
+    let x = u64::symbolic("x");
+    crucible_assume!(x < 10);
+    let chunks = [[1, 2], [3, x]];
+    let mut children = vec![];
+
+    for chunk in chunks.iter().cloned() {
+        children.push(thread::spawn(move || {
+            let mut partial: u64 = 0;
+            for v in chunk.iter() {
+                partial += v;
+            }
+            partial
+        }));
+    }
+
+    let mut sum = 0;
+    for c in children {
+        sum += c.join().unwrap();
+    }
+    crucible_assert!(sum == 6 + x);
+}
+
+#[cfg(with_main)]
+fn main() {}
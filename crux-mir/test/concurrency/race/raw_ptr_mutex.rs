@@ -0,0 +1,39 @@
+extern crate crucible;
+use crucible::*;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// Run with `--concurrency --data-races`.
+
+struct Shared(*mut u32);
+unsafe impl Send for Shared {}
+unsafe impl Sync for Shared {}
+
+// The accesses through the raw pointer are ordered by the lock, by `join`, and
+// by the spawn itself, so none of them race.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let mut x = 0;
+    let p = Arc::new(Shared(&mut x as *mut u32));
+    let lock = Arc::new(Mutex::new(()));
+    unsafe { *p.0 = 1 };
+
+    let mut handles = vec![];
+    for _ in 0..2 {
+        let p = Arc::clone(&p);
+        let lock = Arc::clone(&lock);
+        handles.push(thread::spawn(move || {
+            let _guard = lock.lock().unwrap();
+            unsafe { *p.0 += 1 };
+        }));
+    }
+
+    for h in handles {
+        h.join().unwrap();
+    }
+    crucible_assert!(unsafe { *p.0 } == 3);
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,30 @@
+extern crate crucible;
+use crucible::*;
+use std::sync::Arc;
+use std::thread;
+
+// Run with `--concurrency --data-races`.
+
+struct Shared(*mut u32);
+unsafe impl Send for Shared {}
+unsafe impl Sync for Shared {}
+
+// Both threads increment the counter through a raw pointer with no
+// synchronization, which should be reported as a data race.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_fail() {
+    let mut x = 0;
+    let p = Arc::new(Shared(&mut x as *mut u32));
+
+    let p1 = Arc::clone(&p);
+    let h1 = thread::spawn(move || unsafe { *p1.0 += 1 });
+    let p2 = Arc::clone(&p);
+    let h2 = thread::spawn(move || unsafe { *p2.0 += 1 });
+
+    h1.join().unwrap();
+    h2.join().unwrap();
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,53 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// The writer updates both halves of the pair, with a scheduling point (the atomic store) in
+// between.  Readers also have a scheduling point between their two reads, so without the lock
+// some interleavings would see a torn pair.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let pair        = Arc::new(RwLock::new((0, 0)));
+    let steps       = Arc::new(AtomicU32::new(0));
+    let mut threads = vec![];
+
+    for _ in 0..2 {
+        let p = Arc::clone(&pair);
+        let s = Arc::clone(&steps);
+        threads.push(thread::spawn(move || {
+            let g = p.read().unwrap();
+            let a = g.0;
+            s.load(Ordering::SeqCst);
+            let b = g.1;
+            crucible_assert!(a == b);
+            a + b
+        }));
+    }
+
+    let p = Arc::clone(&pair);
+    let s = Arc::clone(&steps);
+    let writer = thread::spawn(move || {
+        let mut g = p.write().unwrap();
+        g.0 += 1;
+        s.store(1, Ordering::SeqCst);
+        g.1 += 1;
+    });
+
+    for t in threads {
+        let sum = t.join().unwrap();
+        crucible_assert!(sum == 0 || sum == 2);
+    }
+    writer.join().unwrap();
+
+    let g = pair.read().unwrap();
+    crucible_assert!(*g == (1, 1));
+    // Readers share the lock, but a writer can't take it from them.
+    crucible_assert!(pair.try_read().is_ok());
+    crucible_assert!(pair.try_write().is_err());
+}
+
+#[cfg(with_main)]
+fn main() {}
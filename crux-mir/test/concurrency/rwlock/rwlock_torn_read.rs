@@ -0,0 +1,36 @@
+extern crate crucible;
+use crucible::*;
+use std::thread;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// Like `rwlock.rs`, but the writer releases the lock between updating the two halves of the pair,
+// so a reader can see a torn pair.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_fail() {
+    let pair   = Arc::new(RwLock::new((0, 0)));
+    let steps  = Arc::new(AtomicU32::new(0));
+
+    let p = Arc::clone(&pair);
+    let s = Arc::clone(&steps);
+    let reader = thread::spawn(move || {
+        let g = p.read().unwrap();
+        let a = g.0;
+        s.load(Ordering::SeqCst);
+        let b = g.1;
+        crucible_assert!(a == b);
+    });
+
+    let p = Arc::clone(&pair);
+    let writer = thread::spawn(move || {
+        p.write().unwrap().0 += 1;
+        p.write().unwrap().1 += 1;
+    });
+
+    reader.join().unwrap();
+    writer.join().unwrap();
+}
+
+#[cfg(with_main)]
+fn main() {}
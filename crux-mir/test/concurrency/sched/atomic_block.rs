@@ -0,0 +1,36 @@
+extern crate crucible;
+use crucible::*;
+use crucible::sched::{atomic_block, yield_point};
+use std::cell::UnsafeCell;
+use std::sync::Arc;
+use std::thread;
+
+struct Counter(UnsafeCell<usize>);
+
+unsafe impl Sync for Counter {}
+
+fn increment(c: &Counter) -> usize {
+    // The yield point inside the block doesn't let the other thread in.  The nested block ends
+    // before the outer one, which still keeps the other thread out.
+    atomic_block(|| unsafe {
+        let v = atomic_block(|| *c.0.get());
+        yield_point();
+        *c.0.get() = v + 1;
+        v
+    })
+}
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let counter = Arc::new(Counter(UnsafeCell::new(0)));
+    let c = Arc::clone(&counter);
+    let h = thread::spawn(move || increment(&c));
+    let mine = increment(&counter);
+    let theirs = h.join().unwrap();
+    crucible_assert!(mine != theirs);
+    crucible_assert!(unsafe { *counter.0.get() } == 2);
+}
+
+#[cfg(with_main)]
+fn main() {}
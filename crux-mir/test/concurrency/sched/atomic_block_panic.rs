@@ -0,0 +1,26 @@
+extern crate crucible;
+use crucible::*;
+use crucible::sched::atomic_block;
+use std::panic;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let m = Arc::new(Mutex::new(0));
+    let m2 = Arc::clone(&m);
+    let h = thread::spawn(move || *m2.lock().unwrap() += 1);
+
+    // The panic ends the block as it unwinds, so the other thread can run again.  Otherwise,
+    // waiting here for the lock it holds would deadlock.
+    let r = panic::catch_unwind(|| atomic_block(|| panic!("panic in an atomic block")));
+    crucible_assert!(r.is_err());
+    *m.lock().unwrap() += 1;
+
+    h.join().unwrap();
+    crucible_assert!(*m.lock().unwrap() == 2);
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,77 @@
+extern crate crucible;
+use crucible::*;
+use crucible::sched::yield_point;
+use std::cell::UnsafeCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+const N: usize = 2;
+
+struct TicketLock {
+    next_ticket: AtomicUsize,
+    now_serving: AtomicUsize,
+}
+
+impl TicketLock {
+    fn lock(&self) {
+        let ticket = self.next_ticket.fetch_add(1, Ordering::Relaxed);
+        // As with a spin lock, a thread whose turn hasn't come would spin until it does, and
+        // that execution is equivalent to one in which it checked later.
+        if self.now_serving.load(Ordering::Acquire) != ticket {
+            crucible_assume_unreachable!();
+        }
+    }
+
+    fn unlock(&self) {
+        self.now_serving.fetch_add(1, Ordering::Release);
+    }
+}
+
+struct Shared {
+    lock: TicketLock,
+    // Plain memory, which the scheduler doesn't know about.  Without the yield points, each
+    // critical section would run without interruption whether or not the lock worked.
+    in_critical: UnsafeCell<bool>,
+    counter: UnsafeCell<usize>,
+}
+
+unsafe impl Sync for Shared {}
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    let shared = Arc::new(Shared {
+        lock: TicketLock { next_ticket: AtomicUsize::new(0), now_serving: AtomicUsize::new(0) },
+        in_critical: UnsafeCell::new(false),
+        counter: UnsafeCell::new(0),
+    });
+    let mut threads = vec![];
+
+    for _ in 0..N {
+        let s = Arc::clone(&shared);
+        threads.push(thread::spawn(move || {
+            s.lock.lock();
+            unsafe {
+                crucible_assert!(!*s.in_critical.get());
+                *s.in_critical.get() = true;
+                yield_point();
+                let v = *s.counter.get();
+                yield_point();
+                *s.counter.get() = v + 1;
+                yield_point();
+                *s.in_critical.get() = false;
+            }
+            s.lock.unlock();
+        }));
+    }
+
+    for t in threads {
+        t.join().unwrap();
+    }
+
+    crucible_assert!(unsafe { *shared.counter.get() } == N);
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,34 @@
+extern crate crucible;
+use crucible::*;
+use crucible::sched::yield_point;
+use std::cell::UnsafeCell;
+use std::sync::Arc;
+use std::thread;
+
+struct Counter(UnsafeCell<usize>);
+
+unsafe impl Sync for Counter {}
+
+// The yield point lets the other thread run between the read and the write, so one of the
+// increments can be lost.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_fail() {
+    let counter = Arc::new(Counter(UnsafeCell::new(0)));
+    let c = Arc::clone(&counter);
+    let h = thread::spawn(move || unsafe {
+        let v = *c.0.get();
+        yield_point();
+        *c.0.get() = v + 1;
+    });
+    unsafe {
+        let v = *counter.0.get();
+        yield_point();
+        *counter.0.get() = v + 1;
+    }
+    h.join().unwrap();
+    crucible_assert!(unsafe { *counter.0.get() } == 2);
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,40 @@
+extern crate crucible;
+use crucible::*;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    // Joining a scoped thread that panicked returns an `Err`, and `scope`
+    // doesn't panic for it.
+    let joined_err = thread::scope(|s| {
+        let h = s.spawn(|| -> u32 { panic!("scoped thread panicked") });
+        h.join().is_err()
+    });
+    crucible_assert!(joined_err);
+
+    // A panic in a thread that `scope` joins itself makes `scope` panic.
+    let r = panic::catch_unwind(|| {
+        thread::scope(|s| {
+            s.spawn(|| panic!("scoped thread panicked"));
+        })
+    });
+    crucible_assert!(r.is_err());
+
+    // A panic in the closure passed to `scope` still joins its threads before
+    // it continues.
+    let done = AtomicBool::new(false);
+    let r = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        thread::scope(|s| {
+            s.spawn(|| done.store(true, Ordering::SeqCst));
+            panic!("scope closure panicked");
+        })
+    }));
+    crucible_assert!(r.is_err());
+    crucible_assert!(done.load(Ordering::SeqCst));
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -0,0 +1,40 @@
+extern crate crucible;
+use crucible::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::thread;
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    // Scoped threads may borrow from the enclosing stack frame: each one
+    // increments its own half of `data` in place.
+    let mut data = [1, 2, 3, 4];
+    let (left, right) = data.split_at_mut(2);
+    thread::scope(|s| {
+        s.spawn(move || {
+            for v in left.iter_mut() {
+                *v += 10;
+            }
+        });
+        s.spawn(move || {
+            for v in right.iter_mut() {
+                *v += 20;
+            }
+        });
+    });
+    crucible_assert!(data == [11, 12, 23, 24]);
+
+    // Threads may also share a borrow, and their results can be joined
+    // explicitly.
+    let counter = AtomicU32::new(0);
+    let total = thread::scope(|s| {
+        let h1 = s.spawn(|| counter.fetch_add(1, Ordering::SeqCst));
+        let h2 = s.spawn(|| counter.fetch_add(1, Ordering::SeqCst));
+        h1.join().unwrap() + h2.join().unwrap()
+    });
+    crucible_assert!(total == 1);
+    crucible_assert!(counter.load(Ordering::SeqCst) == 2);
+}
+
+#[cfg(with_main)]
+fn main() {}
@@ -34,7 +34,7 @@ fn find_entries(tid:usize,
 
     let mut pcount = count.lock().unwrap();
     *pcount += local_count;
-    count.crucible_TEMP_unlock();
+    drop(pcount);
 }
 
 #[cfg_attr(crux, crux_test)]
@@ -19,7 +19,7 @@ fn cas(tab:&Arc<Vec<Mutex<i32>>>, h:usize, val:i32, new_val:i32) -> i32
         ret_val = 1;
     }
 
-    tab[h].crucible_TEMP_unlock();
+    drop(table_val);
     return ret_val;
 }
 
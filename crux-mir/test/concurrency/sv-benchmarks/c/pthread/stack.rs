@@ -67,7 +67,7 @@ fn t1(stack: Arc<Mutex<Stack>>, with_flag: bool)
         if (with_flag) {
             pstack.flag = true;
         }
-        stack.crucible_TEMP_unlock();
+        drop(pstack);
     }
 }
 
@@ -81,7 +81,7 @@ fn t2(stack: Arc<Mutex<Stack>>, with_flag: bool)
             crucible_assert!(popped != None);
         }
 
-        stack.crucible_TEMP_unlock();
+        drop(pstack);
     }
 }
 
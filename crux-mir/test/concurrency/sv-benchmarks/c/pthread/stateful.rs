@@ -14,11 +14,11 @@ fn run_threads() -> (i32, i32)
     let t1 = thread::spawn(move || {
         let mut pdata1 = p1.lock().unwrap();
         *pdata1 += 1;
-        p1.crucible_TEMP_unlock();
+        drop(pdata1);
 
         let mut pdata2 = q1.lock().unwrap();
         *pdata2 += 1;
-        q1.crucible_TEMP_unlock();
+        drop(pdata2);
     });
 
     let p2 = Arc::clone(&data1);
@@ -26,11 +26,11 @@ fn run_threads() -> (i32, i32)
     let t2 = thread::spawn(move || {
         let mut pdata1 = p2.lock().unwrap();
         *pdata1 += 5;
-        p2.crucible_TEMP_unlock();
+        drop(pdata1);
 
         let mut pdata2 = q2.lock().unwrap();
         *pdata2 -= 6;
-        q2.crucible_TEMP_unlock();
+        drop(pdata2);
     });
 
     t1.join();
@@ -39,8 +39,8 @@ fn run_threads() -> (i32, i32)
     let pdata1 = data1.lock().unwrap();
     let pdata2 = data2.lock().unwrap();
     let r = (*pdata1, *pdata2);
-    data1.crucible_TEMP_unlock();
-    data2.crucible_TEMP_unlock();
+    drop(pdata1);
+    drop(pdata2);
 
     return r
 }
@@ -13,11 +13,11 @@ static data2: AtomicI32 = AtomicI32::new(0);
 fn func_a(lock1: Arc<Mutex<()>>, lock2: Arc<Mutex<()>>) {
     let l = lock1.lock().unwrap();
     data1.store(1, SeqCst);
-    lock1.crucible_TEMP_unlock();
+    drop(l);
 
     let l2 = lock2.lock().unwrap();
     data2.store(data1.load(SeqCst)+1, SeqCst);
-    lock2.crucible_TEMP_unlock();
+    drop(l2);
 }
 
 
@@ -27,15 +27,15 @@ fn func_b(lock1: Arc<Mutex<()>>, lock2: Arc<Mutex<()>>) {
 
     let l = lock1.lock().unwrap();
     if data1.load(SeqCst) == 0 {
-        lock1.crucible_TEMP_unlock();
+        drop(l);
         return ();
     }
     t1 = data1.load(SeqCst);
-    lock1.crucible_TEMP_unlock();
+    drop(l);
 
     let l2 = lock2.lock().unwrap();
     t2 = data2.load(SeqCst);
-    lock2.crucible_TEMP_unlock();
+    drop(l2);
 
     crucible_assert!(t2 == t1 + 1);
 }
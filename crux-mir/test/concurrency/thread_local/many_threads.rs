@@ -0,0 +1,29 @@
+extern crate crucible;
+use crucible::*;
+use std::cell::Cell;
+use std::thread;
+
+thread_local! {
+    static VALUE: Cell<u32> = Cell::new(0);
+}
+
+// Any number of threads can have their own value.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    crucible::max_preemptions(0);
+    for i in 1 .. 21 {
+        let h = thread::spawn(move || {
+            VALUE.with(|v| {
+                crucible_assert!(v.get() == 0);
+                v.set(i);
+            });
+            VALUE.with(|v| v.get())
+        });
+        crucible_assert!(h.join().unwrap() == i);
+    }
+    VALUE.with(|v| crucible_assert!(v.get() == 0));
+}
+
+#[cfg(with_main)]
+fn main() {}
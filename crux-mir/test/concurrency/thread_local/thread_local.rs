@@ -0,0 +1,39 @@
+extern crate crucible;
+use crucible::*;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::thread;
+
+static INITS: AtomicU32 = AtomicU32::new(0);
+
+thread_local! {
+    static ID: Cell<u32> = {
+        INITS.fetch_add(1, Ordering::SeqCst);
+        Cell::new(0)
+    };
+}
+
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_nofail() {
+    ID.with(|id| id.set(100));
+
+    // Each thread starts with its own freshly initialized value, and only sees its own writes.
+    let handles = (1 .. 3).map(|i| thread::spawn(move || {
+        ID.with(|id| {
+            crucible_assert!(id.get() == 0);
+            id.set(i);
+        });
+        ID.with(|id| id.get())
+    })).collect::<Vec<_>>();
+
+    for (i, h) in handles.into_iter().enumerate() {
+        crucible_assert!(h.join().unwrap() == i as u32 + 1);
+    }
+    ID.with(|id| crucible_assert!(id.get() == 100));
+    // The initializer ran once in each thread.
+    crucible_assert!(INITS.load(Ordering::SeqCst) == 3);
+}
+
+#[cfg(with_main)]
+fn main() {}
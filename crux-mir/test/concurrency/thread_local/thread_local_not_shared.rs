@@ -0,0 +1,20 @@
+extern crate crucible;
+use crucible::*;
+use std::cell::Cell;
+use std::thread;
+
+thread_local!(static FLAG: Cell<bool> = Cell::new(false));
+
+// A value written to a thread-local in one thread isn't visible in another.
+#[cfg(not(with_main))]
+#[cfg_attr(crux, crux_test)]
+fn crux_test_fail() {
+    FLAG.with(|f| f.set(true));
+    let h = thread::spawn(|| {
+        FLAG.with(|f| crucible_assert!(f.get()));
+    });
+    h.join().unwrap();
+}
+
+#[cfg(with_main)]
+fn main() {}
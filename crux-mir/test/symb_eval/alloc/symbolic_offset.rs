@@ -0,0 +1,31 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn read_in_range() {
+    let arr: [u32; 4] = [10, 20, 30, 40];
+    let p = arr.as_ptr();
+    let i = nondet_in_range("i", 0, arr.len());
+    let x = unsafe { *p.add(i) };
+    crucible_assert!(x == 10 * (i as u32 + 1));
+}
+
+#[cfg_attr(crux, crux_test)]
+fn write_in_range() {
+    let mut arr = [0u32; 4];
+    let p = arr.as_mut_ptr();
+    let i = nondet_in_range("i", 0, arr.len());
+    unsafe { *p.add(i) = 7 };
+    crucible_assert!(arr[i] == 7);
+    crucible_assert!(arr.iter().filter(|&&x| x == 7).count() == 1);
+}
+
+// A pointer one past the end is valid, though it can't be dereferenced.
+#[cfg_attr(crux, crux_test)]
+fn one_past_end() {
+    let arr: [u32; 4] = [10, 20, 30, 40];
+    let p = arr.as_ptr();
+    let i = nondet_in_range("i", 0, arr.len() + 1);
+    let q = unsafe { p.add(i) };
+    crucible_assert!(unsafe { q.offset_from(p) } == i as isize);
+}
@@ -0,0 +1,10 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn crux_test() {
+    let arr: [u32; 4] = [10, 20, 30, 40];
+    let p = arr.as_ptr();
+    let i = nondet_in_range("i", 0, 6);
+    let _q = unsafe { p.offset(i) };
+}
@@ -0,0 +1,50 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn crux_test() -> usize {
+    let s = [1, 3, 5, 7];
+    let key = u8::symbolic("key");
+    match s.binary_search(&key) {
+        Ok(i) => {
+            crucible_assert!(i < s.len());
+            crucible_assert!(s[i] == key);
+            i
+        },
+        Err(i) => {
+            crucible_assert!(i <= s.len());
+            crucible_assert!(i == 0 || s[i - 1] < key);
+            crucible_assert!(i == s.len() || key < s[i]);
+            i
+        },
+    }
+}
+
+// The elements may be symbolic too.  When they're known to be sorted, a miss means the key isn't
+// there.
+#[cfg_attr(crux, crux_test)]
+fn symbolic_sorted() {
+    let s = <[u8; 3]>::symbolic("s");
+    crucible_assume!(s[0] <= s[1] && s[1] <= s[2]);
+    let key = u8::symbolic("key");
+    if let Ok(i) = s.binary_search(&key) {
+        crucible_assert!(s[i] == key);
+    } else {
+        crucible_assert!(s[0] != key && s[1] != key && s[2] != key);
+    }
+}
+
+// An unsorted slice is searched as in `std`: the result is unspecified, but a match is still a
+// match.
+#[cfg_attr(crux, crux_test)]
+fn symbolic_unsorted() {
+    let s = <[u8; 3]>::symbolic("s");
+    let key = u8::symbolic("key");
+    if let Ok(i) = s.binary_search(&key) {
+        crucible_assert!(s[i] == key);
+    }
+}
+
+pub fn main() {
+    println!("{:?}", crux_test());
+}
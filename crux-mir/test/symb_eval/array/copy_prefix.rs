@@ -0,0 +1,34 @@
+extern crate crucible;
+use crucible::*;
+use std::ptr;
+
+#[cfg_attr(crux, crux_test)]
+fn copy_nonoverlapping_prefix() {
+    let src = <[u8; 8]>::symbolic("src");
+    let mut dest = [0_u8; 8];
+    let n = usize::symbolic_where("n", |&n| n <= 8);
+    unsafe { ptr::copy_nonoverlapping(src.as_ptr(), dest.as_mut_ptr(), n) };
+    for i in 0..8 {
+        if i < n {
+            crucible_assert!(dest[i] == src[i]);
+        } else {
+            crucible_assert!(dest[i] == 0);
+        }
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn copy_overlapping() {
+    let orig = <[u8; 8]>::symbolic("buf");
+    let mut buf = orig;
+    let n = usize::symbolic_where("n", |&n| n <= 6);
+    unsafe { ptr::copy(buf.as_ptr(), buf.as_mut_ptr().add(2), n) };
+    for i in 0..n {
+        crucible_assert!(buf[i + 2] == orig[i]);
+    }
+}
+
+pub fn main() {
+    copy_nonoverlapping_prefix();
+    copy_overlapping();
+}
@@ -0,0 +1,55 @@
+extern crate crucible;
+use crucible::*;
+
+fn count(s: &[u8], x: u8) -> usize {
+    s.iter().filter(|&&y| y == x).count()
+}
+
+fn check_sorted(a: &[u8; 3], b: &[u8; 3]) {
+    crucible_assert!(b[0] <= b[1] && b[1] <= b[2]);
+    for &x in a {
+        crucible_assert!(count(a, x) == count(b, x));
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn sort() {
+    let a = <[u8; 3]>::symbolic("a");
+    let mut b = a;
+    b.sort();
+    check_sorted(&a, &b);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn sort_unstable() {
+    let a = <[u8; 3]>::symbolic("a");
+    let mut b = a;
+    b.sort_unstable();
+    check_sorted(&a, &b);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn sort_by_key_is_stable() {
+    let k = <[u8; 3]>::symbolic("k");
+    let mut v = [(k[0], 0), (k[1], 1), (k[2], 2)];
+    v.sort_by_key(|p| p.0);
+    for i in 1..3 {
+        crucible_assert!(v[i - 1].0 < v[i].0 || v[i - 1].1 < v[i].1);
+    }
+}
+
+// Sorting concrete values runs an ordinary sort, with no symbolic order or assumptions.
+#[cfg_attr(crux, crux_test)]
+fn sort_concrete() {
+    let before = path_stats().assumes;
+    let mut v = [3u8, 1, 2, 1];
+    v.sort();
+    crucible_assert!(v == [1, 1, 2, 3]);
+    let mut w = vec![(2u8, 'a'), (1, 'b'), (2, 'c'), (0, 'd')];
+    w.sort_by_key(|p| p.0);
+    crucible_assert!(w == [(0, 'd'), (1, 'b'), (2, 'a'), (2, 'c')]);
+    let mut u = [5u32, 4, 3];
+    u.sort_unstable();
+    crucible_assert!(u == [3, 4, 5]);
+    crucible_assert!(path_stats().assumes == before);
+}
@@ -0,0 +1,27 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn reverse_twice() {
+    let a = <[u8; 40]>::symbolic("a");
+    let mut b = a;
+    b.reverse();
+    b.reverse();
+    crucible_assert!(a == b);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn nested() -> u16 {
+    let a = <[[u8; 2]; 3]>::symbolic("a");
+    for row in &a {
+        crucible_assume!(row[0] < 10 && row[1] < 10);
+    }
+    let sum = a.iter().flatten().map(|&x| x as u16).sum::<u16>();
+    crucible_assert!(sum < 60);
+    sum
+}
+
+pub fn main() {
+    reverse_twice();
+    println!("{:?}", nested());
+}
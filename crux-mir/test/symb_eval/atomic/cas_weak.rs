@@ -0,0 +1,24 @@
+extern crate crucible;
+use crucible::*;
+use std::sync::atomic::{AtomicU16, Ordering};
+
+// `compare_exchange_weak` may fail spuriously, even though the comparison here always succeeds.
+// Each test asserts that one of the outcomes can't happen, and fails, so both are reachable.
+#[cfg_attr(crux, crux_test)]
+fn can_fail() {
+    let cell = AtomicU16::new(0);
+    let r = cell.compare_exchange_weak(0, 1, Ordering::AcqRel, Ordering::Acquire);
+    crucible_assert!(r.is_ok());
+}
+
+#[cfg_attr(crux, crux_test)]
+fn can_succeed() {
+    let cell = AtomicU16::new(0);
+    let r = cell.compare_exchange_weak(0, 1, Ordering::AcqRel, Ordering::Acquire);
+    crucible_assert!(r.is_err());
+}
+
+pub fn main() {
+    println!("{:?}", can_fail());
+    println!("{:?}", can_succeed());
+}
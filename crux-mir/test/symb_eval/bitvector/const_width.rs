@@ -0,0 +1,55 @@
+extern crate crucible;
+use crucible::bitvector::Bv;
+use crucible::{crucible_assert, crucible_assume};
+use crucible::Symbolic;
+
+type B12 = Bv<12>;
+
+#[cfg_attr(crux, crux_test)]
+fn wrap_around() {
+    crucible_assert!(B12::MAX.to_u64() == 4095);
+    crucible_assert!(B12::MAX + B12::ONE == B12::ZERO);
+    crucible_assert!(B12::ZERO - B12::ONE == B12::MAX);
+    crucible_assert!(B12::from_u64(4096) == B12::ZERO);
+
+    let a = B12::symbolic("a");
+    crucible_assert!(a.to_u64() < 4096);
+    crucible_assume!(a.to_u64() >= 4000);
+    crucible_assert!((a + B12::from_u64(100)).to_u64() == a.to_u64() + 100 - 4096);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn arith() {
+    let a = B12::symbolic("a");
+    let b = B12::symbolic("b");
+    let (x, y) = (a.to_u64(), b.to_u64());
+    crucible_assert!((a + b).to_u64() == (x + y) % 4096);
+    crucible_assert!((a - b).to_u64() == (x + 4096 - y) % 4096);
+    crucible_assert!((a * b).to_u64() == (x * y) % 4096);
+    crucible_assert!((a & b).to_u64() == x & y);
+    crucible_assert!((a | b).to_u64() == x | y);
+    crucible_assert!((a ^ b).to_u64() == x ^ y);
+    crucible_assert!((!a).to_u64() == 4095 - x);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn widths() {
+    let a = B12::symbolic("a");
+    let wide: Bv<16> = a.zero_extend();
+    crucible_assert!(wide.to_u64() == a.to_u64());
+    crucible_assert!((wide + Bv::from_u64(4096)).to_u64() == a.to_u64() + 4096);
+    let low: Bv<4> = a.truncate();
+    crucible_assert!(low.to_u64() == a.to_u64() % 16);
+    crucible_assert!((a >> 4).truncate::<8>().zero_extend::<12>() == a >> 4);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn wide() {
+    type B200 = Bv<200>;
+    crucible_assert!(B200::MAX + B200::ONE == B200::ZERO);
+    crucible_assert!(B200::MAX.truncate::<12>() == B12::MAX);
+    let a = B12::symbolic("a");
+    let wide: B200 = a.zero_extend();
+    crucible_assert!((wide << 188).truncate::<12>() == B12::ZERO);
+    crucible_assert!((wide << 188) >> 188 == wide);
+}
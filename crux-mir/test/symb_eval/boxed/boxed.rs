@@ -0,0 +1,48 @@
+extern crate crucible;
+use crucible::*;
+
+#[derive(Symbolic)]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+#[cfg_attr(crux, crux_test)]
+fn mutate_through_box() {
+    let mut b = Box::new(Point::symbolic("p"));
+    crucible_assume!(b.x < 100);
+    let y = b.y;
+    b.x += 1;
+    crucible_assert!(1 <= b.x && b.x <= 100);
+    crucible_assert!(b.y == y);
+    let p = *b;
+    crucible_assert!(p.x <= 100 && p.y == y);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn identity() {
+    let b = Box::new(u8::symbolic("x"));
+    let r1: *const u8 = &*b;
+    let r2: *const u8 = &*b;
+    crucible_assert!(r1 == r2);
+    let c = Box::new(*b);
+    crucible_assert!(&*c as *const u8 != r1);
+    crucible_assert!(*c == *b);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn nested() {
+    let x = u8::symbolic("x");
+    crucible_assume!(x < 255);
+    let mut bb = Box::new(Box::new(x));
+    **bb += 1;
+    crucible_assert!(**bb == x + 1);
+    let b = *bb;
+    crucible_assert!(*b > x);
+}
+
+pub fn main() {
+    println!("{:?}", mutate_through_box());
+    println!("{:?}", identity());
+    println!("{:?}", nested());
+}
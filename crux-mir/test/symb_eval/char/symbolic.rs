@@ -0,0 +1,23 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn valid_scalar() {
+    let c = char::symbolic("c");
+    let x = c as u32;
+    crucible_assert!(x != 0xD800);
+    crucible_assert!(x <= 0x10FFFF);
+    crucible_assert!(char::from_u32(x) == Some(c));
+}
+
+#[cfg_attr(crux, crux_test)]
+fn ascii_digit() {
+    let c = char::symbolic("c");
+    crucible_assume!(c.is_ascii_digit());
+    crucible_assert!(c.to_digit(10).is_some());
+}
+
+pub fn main() {
+    println!("{:?}", valid_scalar());
+    println!("{:?}", ascii_digit());
+}
@@ -0,0 +1,14 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn crux_test() {
+    let x = u32::symbolic("x");
+    crucible_assume!(x > 10 && x < 13);
+    let c = concretize(x);
+    crucible_assert!(c == 11 || c == 12);
+}
+
+pub fn main() {
+    println!("{:?}", crux_test());
+}
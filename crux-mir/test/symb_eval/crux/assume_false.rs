@@ -0,0 +1,19 @@
+// FLAGS: --check-assumptions
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn crux_test() {
+    crucible_assume!(false);
+    crucible_assert!(false);
+}
+
+// The assumption can hold, but not on the path where `x > 10`.
+#[cfg_attr(crux, crux_test)]
+fn path_unsat() {
+    let x = u8::symbolic("x");
+    if x > 10 {
+        crucible_assume!(x < 5);
+        crucible_assert!(false);
+    }
+}
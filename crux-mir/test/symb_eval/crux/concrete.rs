@@ -0,0 +1,40 @@
+extern crate crucible;
+use crucible::*;
+
+fn classify(x: u8, y: u8) -> u8 {
+    if x > 10 && y == 3 { 0 } else { 1 }
+}
+
+// Pinning the values of a counterexample reproduces the failure.
+#[cfg_attr(crux, crux_test)]
+fn replay_fail() {
+    let x = u8::symbolic("x");
+    let y = u8::symbolic("y");
+    crucible::concrete("x", 11u8);
+    crucible::concrete("y", 3u8);
+    crucible_assert!(classify(x, y) == 1);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn replay_pass() {
+    let x = u8::symbolic("x");
+    let y = u8::symbolic("y");
+    crucible::concrete("x", 10u8);
+    crucible::concrete("y", 3u8);
+    crucible_assert!(classify(x, y) == 1);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn signed_and_bool() {
+    let z = i32::symbolic("z");
+    let b = bool::symbolic("b");
+    crucible::concrete("z", -5i32);
+    crucible::concrete("b", true);
+    crucible_assert!(z == -5);
+    crucible_assert!(b);
+}
+
+pub fn main() {
+    println!("{:?}", replay_pass());
+    println!("{:?}", signed_and_bool());
+}
@@ -0,0 +1,21 @@
+// FLAGS: --distinct-failures
+extern crate crucible;
+use crucible::*;
+
+#[inline(never)]
+fn div(x: i8, y: i8) -> i8 {
+    x / y
+}
+
+// The two checks of a division fail at the same location, but with different
+// messages, so a counterexample is printed for each.  The second call's
+// counterexamples are the same failures and aren't printed.
+#[cfg_attr(crux, crux_test)]
+fn crux_test() {
+    div(i8::symbolic("a"), i8::symbolic("b"));
+    div(i8::symbolic("c"), i8::symbolic("d"));
+}
+
+pub fn main() {
+    println!("{:?}", crux_test());
+}
@@ -0,0 +1,17 @@
+// FLAGS: --distinct-failures
+extern crate crucible;
+use crucible::*;
+
+// Each element can fail the same assertion, with a different message.  Only
+// the first counterexample is printed.
+#[cfg_attr(crux, crux_test)]
+fn crux_test() {
+    let xs = <[u8; 3]>::symbolic("xs");
+    for i in 0..xs.len() {
+        crucible_assert!(xs[i] != 0, "xs[{}] is zero", i);
+    }
+}
+
+pub fn main() {
+    println!("{:?}", crux_test());
+}
@@ -0,0 +1,48 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn one_branch() {
+    let x = u8::symbolic("x");
+    let y = if x < 10 { 1 } else { 2 };
+    let stats = crucible::path_stats();
+    crucible_assert!(stats.branches >= 1);
+    crucible_assert!(y == 1 || y == 2);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn counts_assumptions() {
+    let x = u8::symbolic("x");
+    crucible_assume!(x < 10);
+    crucible_assert!(x + 1 <= 10);
+    let stats = crucible::path_stats();
+    crucible_assert!(stats.assumes >= 1);
+    crucible_assert!(stats.asserts >= 1);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn counts_aborted_paths() {
+    let x = u8::symbolic("x");
+    if x == 0 {
+        crucible_assume!(false);
+    }
+    let stats = crucible::path_stats();
+    crucible_assert!(stats.paths == 2);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn counts_allocations() {
+    let before = crucible::path_stats().allocations;
+    let b = Box::new(1_u8);
+    let mut v = Vec::new();
+    v.push(*b);
+    let stats = crucible::path_stats();
+    crucible_assert!(stats.allocations == before + 2);
+}
+
+pub fn main() {
+    println!("{:?}", one_branch());
+    println!("{:?}", counts_assumptions());
+    println!("{:?}", counts_aborted_paths());
+    println!("{:?}", counts_allocations());
+}
@@ -0,0 +1,13 @@
+extern crate crucible;
+use crucible::*;
+
+// Failures on different paths are reported separately, each with its own counterexample.
+#[cfg_attr(crux, crux_test)]
+fn two_failures() {
+    let x = u8::symbolic("x");
+    if x < 10 {
+        crucible_assert!(x != 3);
+    } else {
+        crucible_assert!(x != 200);
+    }
+}
@@ -0,0 +1,22 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn dead_else() {
+    let x = u8::symbolic("x");
+    if (x as u16) < 256 {
+        crucible_assert!(x as u16 + 1 <= 256);
+    } else {
+        crucible_assert_unreachable!();
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn live_then() {
+    let x = u8::symbolic("x");
+    if (x as u16) < 256 {
+        crucible_assert_unreachable!();
+    } else {
+        crucible_assert!(x as u16 + 1 <= 256);
+    }
+}
@@ -0,0 +1,29 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn bounded() {
+    crucible::unroll(5);
+    let n = usize::symbolic("n");
+    crucible_assume!(n <= 5);
+    let mut count = 0;
+    for _ in 0..n {
+        count += 1;
+    }
+    crucible_assert!(count == n);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn unbounded() {
+    crucible::unroll(5);
+    let n = usize::symbolic("n");
+    let mut count = 0;
+    for _ in 0..n {
+        count += 1;
+    }
+}
+
+pub fn main() {
+    println!("{:?}", bounded());
+    println!("{:?}", unbounded());
+}
@@ -0,0 +1,49 @@
+extern crate crucible;
+use crucible::*;
+
+#[derive(Symbolic)]
+enum Shape {
+    Circle(f64),
+    Rect(f64, f64),
+}
+
+impl Shape {
+    fn is_valid(&self) -> bool {
+        fn ok(x: f64) -> bool { x.is_finite() && x >= 0.0 }
+        match *self {
+            Shape::Circle(r) => ok(r),
+            Shape::Rect(w, h) => ok(w) && ok(h),
+        }
+    }
+
+    fn area(&self) -> f64 {
+        match *self {
+            Shape::Circle(r) => std::f64::consts::PI * r * r,
+            Shape::Rect(w, h) => w * h,
+        }
+    }
+}
+
+#[derive(Symbolic)]
+struct Pair<T> {
+    a: T,
+    b: T,
+}
+
+#[cfg_attr(crux, crux_test)]
+fn area_nonneg() {
+    let s = Shape::symbolic("s");
+    crucible_assume!(s.is_valid());
+    crucible_assert!(s.area() >= 0.0);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn generic_struct() -> u16 {
+    let p = Pair::<u8>::symbolic("p");
+    p.a as u16 + p.b as u16
+}
+
+pub fn main() {
+    area_nonneg();
+    println!("{:?}", generic_struct());
+}
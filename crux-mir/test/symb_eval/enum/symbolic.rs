@@ -0,0 +1,29 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn option_map() {
+    let o = symbolic_option::<u8>("o");
+    crucible_assume!(o != Some(255));
+    crucible_assert!(o.map(|x| x + 1).is_some() == o.is_some());
+}
+
+#[cfg_attr(crux, crux_test)]
+fn result_both_arms() -> u8 {
+    let r = symbolic_result::<u8, u8>("r");
+    match r {
+        Ok(x) => {
+            crucible_assume!(x < 100);
+            x + 100
+        },
+        Err(e) => {
+            crucible_assume!(e < 100);
+            e
+        },
+    }
+}
+
+pub fn main() {
+    option_map();
+    println!("{:?}", result_both_arms());
+}
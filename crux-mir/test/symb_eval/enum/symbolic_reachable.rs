@@ -0,0 +1,34 @@
+extern crate crucible;
+use crucible::*;
+
+// Each test asserts that one variant can't happen, and fails, so every variant is reachable.
+#[cfg_attr(crux, crux_test)]
+fn option_none() {
+    let o = symbolic_option::<u8>("o");
+    crucible_assert!(o.is_some());
+}
+
+#[cfg_attr(crux, crux_test)]
+fn option_some() {
+    let o = symbolic_option::<u8>("o");
+    crucible_assert!(o.is_none());
+}
+
+#[cfg_attr(crux, crux_test)]
+fn result_err() {
+    let r = symbolic_result::<u8, u8>("r");
+    crucible_assert!(r.is_ok());
+}
+
+#[cfg_attr(crux, crux_test)]
+fn result_ok() {
+    let r = symbolic_result::<u8, u8>("r");
+    crucible_assert!(r.is_err());
+}
+
+pub fn main() {
+    option_none();
+    option_some();
+    result_err();
+    result_ok();
+}
@@ -0,0 +1,66 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn nan_to_int() {
+    let x = f32::symbolic("x");
+    if x.is_nan() {
+        crucible_assert!(x as i32 == 0);
+        crucible_assert!(x as u8 == 0);
+        crucible_assert!(x as u128 == 0);
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn inf_to_int() {
+    let x = f64::symbolic("x");
+    if x == f64::INFINITY {
+        crucible_assert!(x as i32 == i32::MAX);
+        crucible_assert!(x as u64 == u64::MAX);
+    }
+    if x == f64::NEG_INFINITY {
+        crucible_assert!(x as i32 == i32::MIN);
+        crucible_assert!(x as u64 == 0);
+    }
+    crucible_assert!(f32::INFINITY as u128 == u128::MAX);
+    crucible_assert!(f32::NEG_INFINITY as i128 == i128::MIN);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn out_of_range() {
+    let x = f64::symbolic("x");
+    if x >= 2147483648.0 {
+        crucible_assert!(x as i32 == i32::MAX);
+    }
+    if x < -2147483648.0 {
+        crucible_assert!(x as i32 == i32::MIN);
+    }
+    if x >= 256.0 {
+        crucible_assert!(x as u8 == u8::MAX);
+    }
+    if x <= -1.0 {
+        crucible_assert!(x as u8 == 0);
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn in_range() {
+    let x = f64::symbolic("x");
+    crucible_assume!(-129.0 < x && x < 128.0);
+    let i = x as i8;
+    // Rounding is toward zero.
+    crucible_assert!((x - i as f64).abs() < 1.0);
+    if x >= 0.0 {
+        crucible_assert!(i as f64 <= x);
+    } else {
+        crucible_assert!(i as f64 >= x);
+    }
+    crucible_assert!((-1.5f64) as i8 == -1);
+    crucible_assert!(255.9f32 as u8 == 255);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn returns_float() -> f64 {
+    let x = 0.5f32;
+    (x as f64) * 3.0
+}
@@ -0,0 +1,22 @@
+extern crate crucible;
+use crucible::*;
+use crucible::symbolic::SymbolicFloat;
+
+#[cfg_attr(crux, crux_test)]
+fn add_sub() {
+    let x = f64::symbolic("x");
+    crucible_assume!(x > 0.0);
+    crucible_assert!((x + 1.0) - 1.0 == x);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn nan_self_eq() {
+    let x = f32::symbolic("x");
+    crucible_assert!(x == x);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn nonnan_self_eq() {
+    let x = f32::symbolic_nonnan("x");
+    crucible_assert!(x == x);
+}
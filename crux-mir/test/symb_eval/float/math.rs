@@ -0,0 +1,43 @@
+extern crate crucible;
+use crucible::*;
+use crucible::symbolic::SymbolicFloat;
+
+#[cfg_attr(crux, crux_test)]
+fn abs_nonneg() {
+    let x = f64::symbolic_nonnan("x");
+    crucible_assert!(x.abs() >= 0.0);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn floor_ceil_bounds() {
+    let x = f64::symbolic_nonnan("x");
+    crucible_assert!(x.floor() <= x);
+    crucible_assert!(x.ceil() >= x);
+    crucible_assert!(x.trunc().abs() <= x.abs());
+}
+
+#[cfg_attr(crux, crux_test)]
+fn copysign_sign() {
+    let x = f32::symbolic_nonnan("x");
+    crucible_assert!(x.copysign(-1.0) <= 0.0);
+    crucible_assert!(x.copysign(1.0) >= 0.0);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn concrete() {
+    crucible_assert!(16.0f64.sqrt() == 4.0);
+    crucible_assert!(2.5f64.round() == 3.0);
+    crucible_assert!((-2.5f64).floor() == -3.0);
+    crucible_assert!((-2.5f64).ceil() == -2.0);
+    crucible_assert!((-2.5f32).trunc() == -2.0);
+    crucible_assert!(3.0f64.copysign(-0.0) == -3.0);
+}
+
+// NaNs have no sign in crux-mir, so `copysign` treats a NaN sign source as positive.  Rust uses
+// the sign bit instead, which gives -3.0 here.
+#[cfg_attr(crux, crux_test)]
+fn copysign_nan() {
+    crucible_assert!(3.0f64.copysign(f64::NAN) == 3.0);
+    crucible_assert!(3.0f64.copysign(-f64::NAN) == 3.0);
+    crucible_assert!((-3.0f32).copysign(-f32::NAN) == 3.0);
+}
@@ -0,0 +1,45 @@
+extern crate crucible;
+use crucible::*;
+
+fn rem(x: f64, y: f64) -> f64 {
+    x % y
+}
+
+// `%` truncates the quotient, so the result has the sign of `x`.
+#[cfg_attr(crux, crux_test)]
+fn truncating() {
+    crucible_assert!(rem(5.0, 3.0) == 2.0);
+    crucible_assert!(rem(-5.0, 3.0) == -2.0);
+    crucible_assert!(rem(5.0, -3.0) == 2.0);
+    crucible_assert!(rem(-5.0, -3.0) == -2.0);
+    crucible_assert!(rem(5.5, 2.0) == 1.5);
+    crucible_assert!(rem(-7.25, 2.0) == -1.25);
+    crucible_assert!(rem(0.75, 0.5) == 0.25);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn special() {
+    crucible_assert!(rem(1.0, 0.0).is_nan());
+    crucible_assert!(rem(f64::INFINITY, 2.0).is_nan());
+    crucible_assert!(rem(3.5, f64::INFINITY) == 3.5);
+    // A zero result keeps the sign of `x`.
+    crucible_assert!(1.0 / rem(-0.0, 2.0) == f64::NEG_INFINITY);
+    crucible_assert!(1.0 / rem(-4.0, 2.0) == f64::NEG_INFINITY);
+    crucible_assert!(1.0 / rem(4.0, -2.0) == f64::INFINITY);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn symbolic_sign() {
+    let x = f32::symbolic("x");
+    let y = f32::symbolic("y");
+    crucible_assume!(x.abs() < f32::INFINITY && y.abs() < f32::INFINITY && y != 0.0);
+    let r = x % y;
+    crucible_assert!(r == 0.0 || (r < 0.0) == (x < 0.0));
+    crucible_assert!(r.abs() < y.abs());
+}
+
+pub fn main() {
+    println!("{:?}", truncating());
+    println!("{:?}", special());
+    println!("{:?}", symbolic_sign());
+}
@@ -0,0 +1,41 @@
+extern crate crucible;
+use crucible::*;
+use std::fmt::Write;
+
+#[cfg_attr(crux, crux_test)]
+fn length() {
+    let n = u8::symbolic("n");
+    let s = format!("{}", n);
+    crucible_assert!(1 <= s.len() && s.len() <= 3);
+    crucible_assert!((s.len() == 1) == (n < 10));
+    crucible_assert!((s.len() == 3) == (n >= 100));
+}
+
+#[cfg_attr(crux, crux_test)]
+fn digits() {
+    let n = u8::symbolic("n");
+    let s = format!("{:?}", n);
+    let b = s.as_bytes();
+    let first = if n >= 100 { n / 100 } else if n >= 10 { n / 10 } else { n };
+    crucible_assert!(b[0] == b'0' + first);
+    crucible_assert!(b[s.len() - 1] == b'0' + n % 10);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn write_signed() {
+    let x = i16::symbolic("x");
+    crucible_assume!(-100 < x && x < 100);
+    let mut s = String::from("x = ");
+    write!(s, "{}", x).unwrap();
+    crucible_assert!(s.starts_with("x = "));
+    crucible_assert!(s.len() >= 5 && s.len() <= 7);
+    crucible_assert!((s.as_bytes()[4] == b'-') == (x < 0));
+}
+
+#[cfg_attr(crux, crux_test)]
+fn concrete() {
+    crucible_assert!(format!("{}", 0u32) == "0");
+    crucible_assert!(format!("{} {}", i64::MIN, u128::MAX)
+        == "-9223372036854775808 340282366920938463463374607431768211455");
+    crucible_assert!(format!("{:4}|{:<3}|{:03}", 7u8, 1i8, -5i32) == "   7|1  |-05");
+}
@@ -0,0 +1,39 @@
+extern crate crucible;
+use crucible::*;
+use std::collections::HashMap;
+
+#[cfg_attr(crux, crux_test)]
+fn insert_get() {
+    let (k, v) = (u32::symbolic("k"), u64::symbolic("v"));
+    let mut m = HashMap::new();
+    m.insert(k, v);
+    crucible_assert!(m.get(&k) == Some(&v));
+    crucible_assert!(m.contains_key(&k));
+    crucible_assert!(m.len() == 1);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn two_keys() {
+    let (k1, k2) = (u32::symbolic("k1"), u32::symbolic("k2"));
+    let mut m = HashMap::new();
+    m.insert(k1, 1u8);
+    let old = m.insert(k2, 2u8);
+    // The second insert replaces the first exactly when the keys are equal.
+    crucible_assert!(old.is_some() == (k1 == k2));
+    crucible_assert!(m.len() == if k1 == k2 { 1 } else { 2 });
+    crucible_assert!(m.get(&k2) == Some(&2));
+    if k1 != k2 {
+        crucible_assert!(m.get(&k1) == Some(&1));
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn insert_remove() {
+    let (k1, k2) = (u32::symbolic("k1"), u32::symbolic("k2"));
+    let mut m = HashMap::new();
+    m.insert(k1, 10u8);
+    let removed = m.remove(&k2);
+    crucible_assert!(removed.is_some() == (k1 == k2));
+    crucible_assert!(m.contains_key(&k1) == (k1 != k2));
+    crucible_assert!(!m.contains_key(&k2));
+}
@@ -0,0 +1,50 @@
+extern crate crucible;
+use crucible::*;
+use std::mem;
+
+#[derive(Clone, Copy, Symbolic)]
+struct Pair {
+    key: u64,
+    val: u8,
+}
+
+#[cfg_attr(crux, crux_test)]
+fn swap_ints() {
+    let (a0, b0) = (u64::symbolic("a"), u64::symbolic("b"));
+    let (mut a, mut b) = (a0, b0);
+    mem::swap(&mut a, &mut b);
+    crucible_assert!(a == b0 && b == a0);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn swap_structs() {
+    let (p0, q0) = (Pair::symbolic("p"), Pair::symbolic("q"));
+    let (mut p, mut q) = (p0, q0);
+    mem::swap(&mut p, &mut q);
+    crucible_assert!(p.key == q0.key && p.val == q0.val);
+    crucible_assert!(q.key == p0.key && q.val == p0.val);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn replace_struct() {
+    let (p0, q0) = (Pair::symbolic("p"), Pair::symbolic("q"));
+    let mut p = p0;
+    let old = mem::replace(&mut p, q0);
+    crucible_assert!(old.key == p0.key && old.val == p0.val);
+    crucible_assert!(p.key == q0.key && p.val == q0.val);
+}
+
+// Compare-and-swap step of a sorting network.
+#[cfg_attr(crux, crux_test)]
+fn sort_network() {
+    let mut v = <[u64; 3]>::symbolic("v");
+    for &(i, j) in &[(0, 1), (1, 2), (0, 1)] {
+        let (mut lo, mut hi) = (v[i], v[j]);
+        if lo > hi {
+            mem::swap(&mut lo, &mut hi);
+        }
+        v[i] = lo;
+        v[j] = hi;
+    }
+    crucible_assert!(v[0] <= v[1] && v[1] <= v[2]);
+}
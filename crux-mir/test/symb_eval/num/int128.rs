@@ -0,0 +1,59 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn u64_product_fits() {
+    let a = u64::symbolic("a");
+    let b = u64::symbolic("b");
+    // Checked for overflow like any other multiplication.
+    let p = (a as u128) * (b as u128);
+    crucible_assert!(p >> 64 < b as u128 || b == 0);
+    crucible_assert!(p as u64 == a.wrapping_mul(b));
+}
+
+#[cfg_attr(crux, crux_test)]
+fn div_rem() {
+    let a = u128::symbolic("a");
+    let b = u8::symbolic("b");
+    crucible_assume!(b != 0);
+    let b = b as u128;
+    crucible_assert!(a / b * b + a % b == a);
+    crucible_assert!(a % b < b);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn shifts() {
+    let x = u64::symbolic("x");
+    let wide = (x as u128) << 64;
+    crucible_assert!(wide >> 64 == x as u128);
+    crucible_assert!(wide as u64 == 0);
+    crucible_assert!(((wide as i128) >> 127 == -1) == (x >> 63 == 1));
+}
+
+#[cfg_attr(crux, crux_test)]
+fn conversions() {
+    let x = u128::symbolic("x");
+    crucible_assert!((x as u64) as u128 == x & u64::MAX as u128);
+    let y = i64::symbolic("y");
+    crucible_assert!((y as i128) as i64 == y);
+    crucible_assert!((y as i128 >= 0) == (y >= 0));
+    crucible_assert!((y as u64) as i128 >= 0);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn signed_difference() {
+    let a = i64::symbolic("a");
+    let b = i64::symbolic("b");
+    // Can't overflow, unlike the same subtraction on `i64`.
+    let d = a as i128 - b as i128;
+    crucible_assert!((d < 0) == (a < b));
+    crucible_assert!(d as i64 == a.wrapping_sub(b));
+}
+
+pub fn main() {
+    println!("{:?}", u64_product_fits());
+    println!("{:?}", div_rem());
+    println!("{:?}", shifts());
+    println!("{:?}", conversions());
+    println!("{:?}", signed_difference());
+}
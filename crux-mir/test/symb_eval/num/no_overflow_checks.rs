@@ -0,0 +1,20 @@
+// FLAGS: --no-overflow-checks
+extern crate crucible;
+use crucible::*;
+
+// Arithmetic wraps instead of failing.
+#[cfg_attr(crux, crux_test)]
+fn wrapping_add() {
+    let x = u8::symbolic("x");
+    crucible_assume!(x > 250);
+    crucible_assert!(x + 10 < 10);
+}
+
+// Dividing `MIN` by -1 still fails, as in a release build.
+#[cfg_attr(crux, crux_test)]
+fn div_overflow() -> i32 {
+    let x = i32::symbolic("x");
+    let y = i32::symbolic("y");
+    crucible_assume!(y != 0);
+    x / y
+}
@@ -0,0 +1,29 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn index_in_bounds() {
+    let v = [1u8, 2, 3, 4, 5];
+    let i = nondet_in_range("i", 0, v.len());
+    crucible_assert!(v[i] >= 1 && v[i] <= 5);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn signed_range() {
+    let x = nondet_in_range::<i32>("x", -3, 3);
+    crucible_assert!(-3 <= x && x < 3);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn symbolic_bounds() {
+    let lo = u8::symbolic("lo");
+    crucible_assume!(lo < 200);
+    let x = nondet_in_range("x", lo, lo + 10);
+    crucible_assert!(x - lo < 10);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn empty_range() {
+    let x = nondet_in_range::<u8>("x", 5, 3);
+    crucible_assert!(x < 5);
+}
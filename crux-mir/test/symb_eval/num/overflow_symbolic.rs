@@ -0,0 +1,23 @@
+extern crate crucible;
+use crucible::*;
+
+#[inline(never)]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[inline(never)]
+fn shl(a: u32, b: u32) -> u32 {
+    a << b
+}
+
+#[cfg_attr(crux, crux_test)]
+fn crux_test() -> u32 {
+    let a = u32::symbolic("a");
+    let b = u32::symbolic("b");
+    add(a, b) ^ shl(a, b)
+}
+
+pub fn main() {
+    println!("{:?}", crux_test());
+}
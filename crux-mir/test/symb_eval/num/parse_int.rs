@@ -0,0 +1,56 @@
+#![feature(crucible_intrinsics)]
+extern crate crucible;
+use crucible::*;
+use std::num::ParseIntError;
+
+#[cfg_attr(crux, crux_test)]
+fn two_digits() {
+    let b = <[u8; 2]>::symbolic("b");
+    crucible_assume!(b[0].is_ascii_digit() && b[1].is_ascii_digit());
+    let s = std::str::from_utf8(&b).unwrap();
+    let v = s.parse::<u32>().unwrap();
+    crucible_assert!(v <= 99);
+    crucible_assert!(v == 10 * (b[0] - b'0') as u32 + (b[1] - b'0') as u32);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn any_bytes() {
+    let b = <[u8; 2]>::symbolic("b");
+    if let Ok(s) = std::str::from_utf8(&b) {
+        match s.parse::<u32>() {
+            Ok(v) => crucible_assert!(v <= 99),
+            Err(_) => crucible_assert!(!(b[0].is_ascii_digit() && b[1].is_ascii_digit())),
+        }
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn bounded_string() {
+    let s = std::string::symbolic_string("s", 3);
+    if let Ok(v) = s.parse::<u16>() {
+        crucible_assert!(v <= 999);
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn overflow() {
+    crucible_assert!("255".parse::<u8>() == Ok(255));
+    crucible_assert!("256".parse::<u8>().is_err());
+    crucible_assert!("-128".parse::<i8>() == Ok(-128));
+    crucible_assert!("-129".parse::<i8>().is_err());
+    crucible_assert!("+7".parse::<i8>() == Ok(7));
+    crucible_assert!("-7".parse::<u8>().is_err());
+    crucible_assert!("".parse::<u8>().is_err());
+    crucible_assert!("1a".parse::<u8>().is_err());
+}
+
+// The errors are the specific ones from `std`, which the golden output shows.
+#[cfg_attr(crux, crux_test)]
+fn overflow_error() -> Result<u8, ParseIntError> {
+    "256".parse::<u8>()
+}
+
+#[cfg_attr(crux, crux_test)]
+fn underflow_error() -> Result<i8, ParseIntError> {
+    "-129".parse::<i8>()
+}
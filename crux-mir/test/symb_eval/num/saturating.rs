@@ -0,0 +1,47 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn add_unsigned() {
+    let y = u8::symbolic("y");
+    crucible_assert!(u8::MAX.saturating_add(y) == u8::MAX);
+    let x = u8::symbolic("x");
+    let z = x.saturating_add(y);
+    crucible_assert!(z >= x && z >= y);
+    crucible_assert!(z == u8::MAX || z == x + y);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn sub_signed() {
+    let y = i8::symbolic("y");
+    crucible_assume!(y >= 0);
+    crucible_assert!(i8::MIN.saturating_sub(y) == i8::MIN);
+    let x = i8::symbolic("x");
+    let z = x.saturating_sub(y);
+    crucible_assert!(z <= x);
+    crucible_assert!(z == i8::MIN || z == x - y);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn mul_unsigned() {
+    let x = u16::symbolic("x");
+    let y = u16::symbolic("y");
+    let z = x.saturating_mul(y);
+    crucible_assert!(z == u16::MAX || z as u32 == x as u32 * y as u32);
+    crucible_assert!((x as u32 * y as u32 <= u16::MAX as u32) == (z as u32 == x as u32 * y as u32));
+}
+
+#[cfg_attr(crux, crux_test)]
+fn mul_signed() {
+    let x = i8::symbolic("x");
+    let y = i8::symbolic("y");
+    let z = x.saturating_mul(y);
+    let exact = x as i32 * y as i32;
+    if exact > i8::MAX as i32 {
+        crucible_assert!(z == i8::MAX);
+    } else if exact < i8::MIN as i32 {
+        crucible_assert!(z == i8::MIN);
+    } else {
+        crucible_assert!(z as i32 == exact);
+    }
+}
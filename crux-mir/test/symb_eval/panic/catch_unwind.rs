@@ -0,0 +1,57 @@
+extern crate crucible;
+use crucible::*;
+use std::cell::Cell;
+use std::panic;
+
+struct Guard<'a>(&'a Cell<u32>);
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn index_out_of_range() {
+    let v = vec![10_u8, 20, 30];
+    let i = usize::symbolic("i");
+    match panic::catch_unwind(|| v[i]) {
+        Ok(x) => crucible_assert!(i < 3 && x == v[i]),
+        Err(_) => crucible_assert!(i >= 3),
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn unwrap_none() {
+    let o = symbolic_option::<u32>("o");
+    let r = panic::catch_unwind(|| o.unwrap() / 2);
+    crucible_assert!(r.is_ok() == o.is_some());
+}
+
+#[cfg_attr(crux, crux_test)]
+fn drops_while_unwinding() {
+    let drops = Cell::new(0);
+    let fail = bool::symbolic("fail");
+    let r = panic::catch_unwind(|| {
+        let _g = Guard(&drops);
+        if fail {
+            panic!("failed");
+        }
+        5
+    });
+    crucible_assert!(r.is_err() == fail);
+    crucible_assert!(drops.get() == 1);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn nested() {
+    let x = u8::symbolic("x");
+    let r = panic::catch_unwind(|| {
+        let inner = panic::catch_unwind(|| 100 / x);
+        inner.unwrap_or(0) + 1
+    });
+    match r {
+        Ok(y) => crucible_assert!(y == if x == 0 { 1 } else { 100 / x + 1 }),
+        Err(_) => crucible_assert!(false),
+    }
+}
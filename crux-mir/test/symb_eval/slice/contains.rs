@@ -0,0 +1,60 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn concrete() {
+    let v = [1, 2, 3, 2];
+    crucible_assert!(v.contains(&3));
+    crucible_assert!(!v.contains(&4));
+    crucible_assert!(b"hello".contains(&b'l'));
+
+    let mut it = v.iter();
+    crucible_assert!(it.position(|&x| x == 2) == Some(1));
+    crucible_assert!(it.next() == Some(&3));
+    crucible_assert!(it.position(|&x| x == 5) == None);
+    crucible_assert!(it.next() == None);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn consistent() {
+    let v = <[u8; 4]>::symbolic("v");
+    let k = u8::symbolic("k");
+    crucible_assert!(v.contains(&k) == v.iter().position(|x| *x == k).is_some());
+}
+
+#[cfg_attr(crux, crux_test)]
+fn first_match() {
+    let v = <[u8; 4]>::symbolic("v");
+    let k = u8::symbolic("k");
+    match v.iter().position(|x| *x == k) {
+        Some(i) => {
+            crucible_assert!(v[i] == k);
+            for j in 0..i {
+                crucible_assert!(v[j] != k);
+            }
+        },
+        None => {
+            for j in 0..v.len() {
+                crucible_assert!(v[j] != k);
+            }
+        },
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn stops_calling() {
+    let v = <[u8; 4]>::symbolic("v");
+    let mut calls = 0;
+    let found = v.iter().position(|x| { calls += 1; *x == 0 });
+    match found {
+        Some(i) => crucible_assert!(calls == i + 1),
+        None => crucible_assert!(calls == v.len()),
+    }
+}
+
+pub fn main() {
+    println!("{:?}", concrete());
+    println!("{:?}", consistent());
+    println!("{:?}", first_match());
+    println!("{:?}", stops_calling());
+}
@@ -0,0 +1,51 @@
+extern crate crucible;
+use crucible::*;
+use std::panic;
+
+#[cfg_attr(crux, crux_test)]
+fn lengths() {
+    let v = [1, 2, 3, 4, 5, 6];
+    let mid = nondet_in_range("mid", 0, v.len() + 1);
+    let (left, right) = v.split_at(mid);
+    crucible_assert!(left.len() == mid);
+    crucible_assert!(left.len() + right.len() == v.len());
+}
+
+#[cfg_attr(crux, crux_test)]
+fn contents() {
+    let v = <[u8; 4]>::symbolic("v");
+    let mid = nondet_in_range("mid", 0, v.len() + 1);
+    let (left, right) = v.split_at(mid);
+    let i = nondet_in_range("i", 0, v.len());
+    if i < mid {
+        crucible_assert!(left[i] == v[i]);
+    } else {
+        crucible_assert!(right[i - mid] == v[i]);
+    }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn split_mut() {
+    let mut v = [0_u8; 4];
+    let mid = nondet_in_range("mid", 1, v.len());
+    {
+        let (left, right) = v.split_at_mut(mid);
+        left[mid - 1] = 1;
+        right[0] = 2;
+    }
+    crucible_assert!(v[mid - 1] == 1 && v[mid] == 2);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn out_of_range() {
+    let v = [1, 2, 3];
+    let mid = usize::symbolic_where("mid", |&m| m > 3);
+    let r = panic::catch_unwind(|| { v.split_at(mid); });
+    crucible_assert!(r.is_err());
+}
+
+pub fn main() {
+    println!("{:?}", lengths());
+    println!("{:?}", contents());
+    println!("{:?}", split_mut());
+}
@@ -0,0 +1,25 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn add_commutes() {
+    let (a, b) = <(i32, i32)>::symbolic("t");
+    crucible_assume!(-1000 < a && a < 1000 && -1000 < b && b < 1000);
+    crucible_assert!(a + b == b + a);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn mixed_components() -> u32 {
+    let (x, flag, c) = <(u8, bool, char)>::symbolic("t");
+    crucible_assert!((c as u32) < 0xD800 || (c as u32) > 0xDFFF);
+    if flag { x as u32 } else { c as u32 }
+}
+
+#[cfg_attr(crux, crux_test)]
+fn nested_option() -> u16 {
+    match symbolic_option::<((u8, u8), bool)>("o") {
+        Some(((lo, hi), true)) => lo as u16 + hi as u16,
+        Some((_, false)) => 1000,
+        None => 0,
+    }
+}
@@ -0,0 +1,39 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn dedup_concrete() {
+    // Concrete comparisons run the original loop, with no symbolic order or assumptions.
+    let before = path_stats().assumes;
+    let mut v = vec![1, 1, 2, 3, 3, 3, 1];
+    v.dedup();
+    crucible_assert!(v == [1, 2, 3, 1]);
+
+    let mut v = vec![10, 20, 21, 30, 20];
+    v.dedup_by_key(|i| *i / 10);
+    crucible_assert!(v == [10, 20, 30, 20]);
+    crucible_assert!(path_stats().assumes == before);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn dedup_symbolic() {
+    let orig = <[u8; 4]>::symbolic("x");
+    let mut v = orig.to_vec();
+    v.dedup();
+    crucible_assert!(1 <= v.len() && v.len() <= orig.len());
+    crucible_assert!(v[0] == orig[0]);
+    for i in 1..v.len() {
+        crucible_assert!(v[i] != v[i - 1]);
+    }
+    if orig[0] == orig[1] && orig[1] == orig[2] && orig[2] == orig[3] {
+        crucible_assert!(v.len() == 1);
+    }
+    if orig[0] != orig[1] && orig[1] != orig[2] && orig[2] != orig[3] {
+        crucible_assert!(v == orig);
+    }
+}
+
+pub fn main() {
+    println!("{:?}", dedup_concrete());
+    println!("{:?}", dedup_symbolic());
+}
@@ -0,0 +1,45 @@
+#![feature(crucible_intrinsics)]
+extern crate crucible;
+use crucible::*;
+use std::vec::symbolic_vec;
+
+#[cfg_attr(crux, crux_test)]
+fn sum_bounded() {
+    let v = symbolic_vec::<u8>("v", 16);
+    let sum = v.iter().map(|&x| x as u64).sum::<u64>();
+    crucible_assert!(sum >= 0);
+    crucible_assert!(sum <= 255 * v.len() as u64);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn sum_copied() {
+    let v = symbolic_vec::<u32>("v", 16);
+    v.iter().for_each(|&x| crucible_assume!(x < 1000));
+    let sum = v.iter().copied().sum::<u32>();
+    crucible_assert!(sum < 16000);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn product_bounded() {
+    let v = symbolic_vec::<u8>("v", 2);
+    let product = v.iter().map(|&x| x as u32).product::<u32>();
+    crucible_assert!(product <= 255 * 255);
+    crucible_assert!(v.len() != 0 || product == 1);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn fold_count() {
+    let mut v = symbolic_vec::<u8>("v", 8);
+    let n = v.iter().fold(0, |n, _| n + 1);
+    crucible_assert!(n == v.len());
+    v.iter_mut().for_each(|x| *x = 1);
+    let ones = v.iter_mut().fold(0, |n, x| n + *x as usize);
+    crucible_assert!(ones == n);
+}
+
+pub fn main() {
+    println!("{:?}", sum_bounded());
+    println!("{:?}", sum_copied());
+    println!("{:?}", product_bounded());
+    println!("{:?}", fold_count());
+}
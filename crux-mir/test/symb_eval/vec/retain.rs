@@ -0,0 +1,37 @@
+extern crate crucible;
+use crucible::*;
+
+#[cfg_attr(crux, crux_test)]
+fn retain_concrete() {
+    let mut v = vec![1, 2, 3, 4];
+    v.retain(|&x| x % 2 == 0);
+    crucible_assert!(v == [2, 4]);
+
+    // The predicate is still called on each element in order.
+    let mut v = vec![1, 2, 3, 4, 5];
+    let keep = [false, true, true, false, true];
+    let mut i = 0;
+    v.retain(|_| (keep[i], i += 1).0);
+    crucible_assert!(v == [2, 3, 5]);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn retain_symbolic() {
+    let orig = <[u32; 4]>::symbolic("x");
+    let mut v = orig.iter().cloned().enumerate().collect::<Vec<_>>();
+    v.retain(|&(_, x)| x % 2 == 0);
+    crucible_assert!(v.len() <= orig.len());
+    crucible_assert!(v.len() == orig.iter().filter(|&&x| x % 2 == 0).count());
+    for &(i, x) in &v {
+        crucible_assert!(x % 2 == 0);
+        crucible_assert!(x == orig[i]);
+    }
+    for j in 1..v.len() {
+        crucible_assert!(v[j - 1].0 < v[j].0);
+    }
+}
+
+pub fn main() {
+    println!("{:?}", retain_concrete());
+    println!("{:?}", retain_symbolic());
+}
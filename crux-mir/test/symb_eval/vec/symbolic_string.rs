@@ -0,0 +1,20 @@
+#![feature(crucible_intrinsics)]
+extern crate crucible;
+use crucible::*;
+use std::str;
+use std::string::symbolic_string;
+
+#[cfg_attr(crux, crux_test)]
+fn valid_utf8() {
+    let s = symbolic_string("s", 4);
+    crucible_assert!(s.len() <= 4);
+    crucible_assert!(str::from_utf8(s.as_bytes()).is_ok());
+    crucible_assert!(s.chars().count() <= s.len());
+}
+
+#[cfg_attr(crux, crux_test)]
+fn round_trip() {
+    let s = symbolic_string("s", 3);
+    let t = s.chars().collect::<String>();
+    crucible_assert!(s == t);
+}
@@ -0,0 +1,23 @@
+#![feature(crucible_intrinsics)]
+extern crate crucible;
+use crucible::*;
+use std::vec::symbolic_vec;
+
+#[cfg_attr(crux, crux_test)]
+fn len_bound() {
+    let mut v = symbolic_vec::<u8>("v", 3);
+    crucible_assert!(v.len() <= 3);
+    let n = v.len();
+    v.push(1);
+    crucible_assert!(v.len() == n + 1);
+    crucible_assert!(v[n] == 1);
+}
+
+#[cfg_attr(crux, crux_test)]
+fn sum_first() {
+    let v = symbolic_vec::<u8>("v", 3);
+    let sum = v.iter().map(|&x| x as u32).sum::<u32>();
+    // Fails only for the empty vector, which has no first element.
+    let first = v.first().map_or(1, |&x| x as u32);
+    crucible_assert!(sum >= first);
+}
@@ -85,7 +85,11 @@ translate lib/libcore/lib.rs --crate-name core \
 
 translate_2015 lib/compiler-builtins/src/lib.rs --crate-name compiler_builtins \
     --cfg 'feature="compiler-builtins"'
-translate lib/crucible/lib.rs --crate-name crucible
+# `crucible_derive` is a proc macro, which runs inside the compiler, so it's
+# compiled natively instead of being translated.
+rustc -L rlibs --out-dir rlibs --edition 2018 --crate-type proc-macro \
+    lib/crucible_derive/lib.rs --crate-name crucible_derive
+translate lib/crucible/lib.rs --crate-name crucible --extern crucible_derive
 translate lib/int512.rs
 translate lib/liballoc/lib.rs --crate-name alloc \
     ${CONCURRENCY_LIBALLOC_FEATURES} \